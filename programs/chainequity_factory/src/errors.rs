@@ -17,6 +17,9 @@ pub enum FactoryError {
     #[msg("Initial supply must be greater than zero")]
     ZeroSupply,
 
+    #[msg("Initial supply does not leave headroom for the maximum split multiplier")]
+    InvalidSupply,
+
     #[msg("Invalid threshold - must have at least threshold signers")]
     InvalidThreshold,
 
@@ -51,6 +54,12 @@ pub enum FactoryError {
     #[msg("Signer has already approved this transaction")]
     AlreadyApproved,
 
+    #[msg("Signer has not approved this transaction")]
+    NotApproved,
+
+    #[msg("Cannot revoke the last approval; cancel the transaction instead")]
+    CannotRevokeLastApproval,
+
     #[msg("Transaction has expired")]
     TransactionExpired,
 
@@ -59,4 +68,25 @@ pub enum FactoryError {
 
     #[msg("Threshold not met: need more approvals")]
     ThresholdNotMet,
+
+    #[msg("Deadline must be in the future")]
+    InvalidDeadline,
+
+    #[msg("Duplicate signer in signer list")]
+    DuplicateSigner,
+
+    #[msg("Signer list cannot be empty")]
+    NoSigners,
+
+    #[msg("Token ID does not exist in this factory")]
+    InvalidTokenId,
+
+    #[msg("Symbol is already in use by another token in this factory")]
+    SymbolTaken,
+
+    #[msg("Treasury must be a valid wallet address")]
+    InvalidTreasury,
+
+    #[msg("Decimals exceed the maximum (9)")]
+    InvalidDecimals,
 }
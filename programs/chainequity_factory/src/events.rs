@@ -16,6 +16,8 @@ pub struct TokenCreated {
     pub mint: Pubkey,
     pub token_config: Pubkey,
     pub initial_supply: u64,
+    /// Decimals for rendering share amounts
+    pub display_decimals: u8,
     pub features: TokenFeatures,
     pub admin_threshold: u8,
     pub created_by: Pubkey,
@@ -34,3 +36,32 @@ pub struct FactoryPausedChanged {
     pub paused: bool,
     pub changed_by: Pubkey,
 }
+
+#[event]
+pub struct MintAuthorityTransferred {
+    pub token_config: Pubkey,
+    pub mint: Pubkey,
+    pub new_authority: Pubkey,
+    pub transferred_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SymbolReassigned {
+    pub factory: Pubkey,
+    pub token_config: Pubkey,
+    pub old_symbol: String,
+    pub new_symbol: String,
+    pub changed_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ApprovalRevoked {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub transaction_id: u64,
+    pub revoked_by: Pubkey,
+    pub remaining_approvals: u8,
+    pub slot: u64,
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token_2022::{self, Token2022, SetAuthority};
 use anchor_spl::token_interface::Mint;
 use anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType;
-use crate::state::{TokenFactory, FACTORY_SEED};
-use crate::instructions::create_token::{TokenConfig, TOKEN_CONFIG_SEED};
+use crate::state::{TokenFactory, SymbolRegistry, FACTORY_SEED, SYMBOL_SEED};
+use crate::instructions::create_token::{TokenConfig, MultisigConfig, TOKEN_CONFIG_SEED};
 use crate::errors::FactoryError;
-use crate::events::FactoryPausedChanged;
+use crate::events::{FactoryPausedChanged, MintAuthorityTransferred, SymbolReassigned};
 
 #[derive(Accounts)]
 pub struct SetFactoryPaused<'info> {
@@ -91,6 +92,14 @@ pub fn transfer_mint_authority_handler(ctx: Context<TransferMintAuthority>) -> R
         Some(ctx.accounts.new_authority.key()),
     )?;
 
+    emit!(MintAuthorityTransferred {
+        token_config: token_config.key(),
+        mint: ctx.accounts.mint.key(),
+        new_authority: ctx.accounts.new_authority.key(),
+        transferred_by: ctx.accounts.authority.key(),
+        slot: Clock::get()?.slot,
+    });
+
     msg!(
         "Mint authority transferred to: {} for token: {}",
         ctx.accounts.new_authority.key(),
@@ -99,3 +108,108 @@ pub fn transfer_mint_authority_handler(ctx: Context<TransferMintAuthority>) -> R
 
     Ok(())
 }
+
+// ============================================================================
+// TOKEN REGISTRY
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GetTokenConfigAddress<'info> {
+    #[account(
+        seeds = [FACTORY_SEED],
+        bump = factory.bump,
+    )]
+    pub factory: Account<'info, TokenFactory>,
+}
+
+/// Read-only: returns the token config address for `token_id` via return data.
+/// Iterating 0..token_count enumerates every token created by the factory.
+pub fn get_token_config_address_handler(ctx: Context<GetTokenConfigAddress>, token_id: u64) -> Result<()> {
+    let factory = &ctx.accounts.factory;
+    require!(token_id < factory.token_count, FactoryError::InvalidTokenId);
+
+    let address = TokenConfig::address(&factory.key(), token_id);
+    set_return_data(address.as_ref());
+
+    Ok(())
+}
+
+// ============================================================================
+// SYMBOL REGISTRY
+// ============================================================================
+
+/// Move a token to a new symbol: releases the old registry entry and claims the new one.
+/// Called directly or via CPI from the token program's change_symbol.
+#[derive(Accounts)]
+#[instruction(new_symbol: String)]
+pub struct ReassignSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.factory.as_ref(), &token_config.token_id.to_le_bytes()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ FactoryError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ FactoryError::Unauthorized,
+    )]
+    pub multisig: Box<Account<'info, MultisigConfig>>,
+
+    /// Registry entry for the current symbol, closed to free it
+    #[account(
+        mut,
+        seeds = [SYMBOL_SEED, token_config.factory.as_ref(), token_config.symbol.as_bytes()],
+        bump = old_symbol_registry.bump,
+        constraint = old_symbol_registry.token_config == token_config.key() @ FactoryError::Unauthorized,
+        close = authority,
+    )]
+    pub old_symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolRegistry::LEN,
+        seeds = [SYMBOL_SEED, token_config.factory.as_ref(), new_symbol.as_bytes()],
+        bump
+    )]
+    pub new_symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn reassign_symbol_handler(ctx: Context<ReassignSymbol>, new_symbol: String) -> Result<()> {
+    require!(!new_symbol.is_empty(), FactoryError::SymbolEmpty);
+    require!(new_symbol.len() <= 10, FactoryError::SymbolTooLong);
+    require!(
+        ctx.accounts.new_symbol_registry.token_config == Pubkey::default(),
+        FactoryError::SymbolTaken
+    );
+
+    let token_config = &mut ctx.accounts.token_config;
+    let old_symbol = token_config.symbol.clone();
+    token_config.symbol = new_symbol.clone();
+
+    let registry = &mut ctx.accounts.new_symbol_registry;
+    registry.factory = token_config.factory;
+    registry.token_config = token_config.key();
+    registry.symbol = new_symbol.clone();
+    registry.bump = ctx.bumps.new_symbol_registry;
+
+    emit!(SymbolReassigned {
+        factory: token_config.factory,
+        token_config: token_config.key(),
+        old_symbol,
+        new_symbol: new_symbol.clone(),
+        changed_by: ctx.accounts.authority.key(),
+        slot: Clock::get()?.slot,
+    });
+
+    msg!("Symbol reassigned to: {}", new_symbol);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint, TokenAccount};
 
-use crate::state::{TokenFactory, CreateTokenParams, TokenFeatures, FACTORY_SEED};
+use crate::state::{TokenFactory, CreateTokenParams, TokenFeatures, SymbolRegistry, FACTORY_SEED, SYMBOL_SEED};
 use crate::errors::FactoryError;
 use crate::events::TokenCreated;
 
@@ -23,14 +23,40 @@ pub struct TokenConfig {
     pub name: String,
     /// Token decimals
     pub decimals: u8,
+    /// Decimals clients use to render share amounts (reporting only; on-chain math uses raw units)
+    pub display_decimals: u8,
     /// Current total supply
     pub total_supply: u64,
     /// For virtual split (default 1)
     pub split_multiplier: u64,
     /// Enabled features
     pub features: TokenFeatures,
+    /// Number of wallets with a non-zero balance
+    pub holder_count: u32,
+    /// Number of wallets with an allowlist entry
+    pub allowlist_count: u64,
+    /// Cap on allowlisted wallets, e.g. for holder-limited exemptions (None = no cap)
+    pub max_holders: Option<u32>,
+    /// Dividend rounds created (next round ID)
+    pub dividend_round_count: u64,
+    /// Mints accepted as dividend payment tokens (empty = any)
+    pub allowed_payment_tokens: Vec<Pubkey>,
+    /// Daily transfer cap for each allowlist KYC level (None = no tier cap)
+    pub kyc_level_limits: [Option<u64>; 4],
+    /// Token-wide daily transfer limit (running total per UTC day) for wallets without their own (set by governance)
+    pub default_daily_limit: Option<u64>,
+    /// Token-wide lockout for wallets without WalletRestrictions (set by governance)
+    pub default_lockout_until: Option<i64>,
+    /// Transfer fee in basis points (None = no fee)
+    pub transfer_fee_bps: Option<u16>,
+    /// Token account receiving transfer fees
+    pub fee_treasury: Pubkey,
+    /// Wallet that must own accounts receiving forfeited or returned shares
+    pub treasury: Pubkey,
     /// Emergency pause
     pub is_paused: bool,
+    /// False once the token is retired (terminal - cannot be reactivated)
+    pub is_active: bool,
     /// Seconds before upgrade executes
     pub upgrade_timelock: i64,
     /// Creation timestamp
@@ -40,6 +66,15 @@ pub struct TokenConfig {
 }
 
 impl TokenConfig {
+    pub const KYC_LEVELS: usize = 4;
+    /// Maximum mint decimals accepted at creation
+    pub const MAX_DECIMALS: u8 = 9;
+    /// Largest cumulative split multiplier the initial supply must survive without overflow
+    pub const MAX_SPLIT_MULTIPLIER: u64 = 1_000;
+    /// Floor for upgrade_timelock so it can't be set to zero to bypass it (1 hour)
+    pub const MIN_UPGRADE_TIMELOCK: i64 = 3600;
+    /// Maximum entries in allowed_payment_tokens
+    pub const MAX_PAYMENT_TOKENS: usize = 8;
     pub const LEN: usize = 8 + // discriminator
         32 + // factory
         8 +  // token_id
@@ -48,13 +83,34 @@ impl TokenConfig {
         (4 + 10) + // symbol
         (4 + 50) + // name
         1 +  // decimals
+        1 +  // display_decimals
         8 +  // total_supply
         8 +  // split_multiplier
         TokenFeatures::LEN +
+        4 +  // holder_count
+        8 +  // allowlist_count
+        (1 + 4) + // max_holders Option<u32>
+        8 +  // dividend_round_count
+        (4 + 32 * Self::MAX_PAYMENT_TOKENS) + // allowed_payment_tokens
+        (1 + 8) * Self::KYC_LEVELS + // kyc_level_limits
+        (1 + 8) + // default_daily_limit Option<u64>
+        (1 + 8) + // default_lockout_until Option<i64>
+        (1 + 2) + // transfer_fee_bps Option<u16>
+        32 + // fee_treasury
+        32 + // treasury
         1 +  // is_paused
+        1 +  // is_active
         8 +  // upgrade_timelock
         8 +  // created_at
         1;   // bump
+
+    /// Derive the token config address for the token with the given ID on a factory
+    pub fn address(factory: &Pubkey, token_id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[TOKEN_CONFIG_SEED, factory.as_ref(), &token_id.to_le_bytes()],
+            &crate::ID,
+        ).0
+    }
 }
 
 /// Multi-sig configuration for token admin
@@ -113,6 +169,16 @@ pub struct CreateToken<'info> {
     )]
     pub multisig: Box<Account<'info, MultisigConfig>>,
 
+    /// Claims the symbol for this token; an existing claim means the symbol is taken
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SymbolRegistry::LEN,
+        seeds = [SYMBOL_SEED, factory.key().as_ref(), params.symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
     /// The mint account for the new token (Token-2022)
     #[account(
         init,
@@ -139,9 +205,19 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
     require!(!params.symbol.is_empty(), FactoryError::SymbolEmpty);
     require!(params.symbol.len() <= 10, FactoryError::SymbolTooLong);
     require!(params.name.len() <= 50, FactoryError::NameTooLong);
+    require!(
+        ctx.accounts.symbol_registry.token_config == Pubkey::default(),
+        FactoryError::SymbolTaken
+    );
     require!(params.initial_supply > 0, FactoryError::ZeroSupply);
+    require!(params.decimals <= TokenConfig::MAX_DECIMALS, FactoryError::InvalidDecimals);
+    require!(
+        params.initial_supply.checked_mul(TokenConfig::MAX_SPLIT_MULTIPLIER).is_some(),
+        FactoryError::InvalidSupply
+    );
     require!(params.admin_signers.len() <= MultisigConfig::MAX_SIGNERS, FactoryError::TooManySigners);
     require!(params.admin_threshold >= 1, FactoryError::ThresholdTooLow);
+    require_keys_neq!(params.treasury, Pubkey::default(), FactoryError::InvalidTreasury);
     require!(
         params.admin_signers.len() >= params.admin_threshold as usize,
         FactoryError::InvalidThreshold
@@ -162,10 +238,23 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
     token_config.symbol = params.symbol.clone();
     token_config.name = params.name.clone();
     token_config.decimals = params.decimals;
+    token_config.display_decimals = params.decimals;
     token_config.total_supply = params.initial_supply;
     token_config.split_multiplier = 1;
     token_config.features = params.features.clone();
+    token_config.holder_count = 0;
+    token_config.allowlist_count = 0;
+    token_config.max_holders = None;
+    token_config.dividend_round_count = 0;
+    token_config.allowed_payment_tokens = Vec::new();
+    token_config.kyc_level_limits = [None; TokenConfig::KYC_LEVELS];
+    token_config.default_daily_limit = None;
+    token_config.default_lockout_until = None;
+    token_config.transfer_fee_bps = None;
+    token_config.fee_treasury = Pubkey::default();
+    token_config.treasury = params.treasury;
     token_config.is_paused = false;
+    token_config.is_active = true;
     token_config.upgrade_timelock = 86400; // 24 hours default
     token_config.created_at = Clock::get()?.unix_timestamp;
     token_config.bump = ctx.bumps.token_config;
@@ -178,6 +267,12 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
     multisig.nonce = 0;
     multisig.bump = ctx.bumps.multisig;
 
+    let symbol_registry = &mut ctx.accounts.symbol_registry;
+    symbol_registry.factory = factory.key();
+    symbol_registry.token_config = token_config.key();
+    symbol_registry.symbol = params.symbol.clone();
+    symbol_registry.bump = ctx.bumps.symbol_registry;
+
     let clock = Clock::get()?;
 
     emit!(TokenCreated {
@@ -188,6 +283,7 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
         mint: ctx.accounts.mint.key(),
         token_config: token_config.key(),
         initial_supply: params.initial_supply,
+        display_decimals: token_config.display_decimals,
         features: params.features,
         admin_threshold: params.admin_threshold,
         created_by: ctx.accounts.payer.key(),
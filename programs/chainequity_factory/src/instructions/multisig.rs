@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{MultiSig, MultiSigTransaction, TransactionType, MULTISIG_SEED, TRANSACTION_SEED};
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::{MultiSig, MultiSigTransaction, MultiSigTransactionState, TransactionType, MULTISIG_SEED, TRANSACTION_SEED};
 use crate::errors::FactoryError;
+use crate::events::ApprovalRevoked;
 
 /// Initialize a multi-sig wallet for token administration
 pub fn init_multisig(
@@ -16,8 +18,17 @@ pub fn init_multisig(
         signers.len() <= MultiSig::MAX_SIGNERS,
         FactoryError::TooManySigners
     );
+    require!(!signers.is_empty(), FactoryError::NoSigners);
     require!(threshold > 0, FactoryError::InvalidThreshold);
 
+    // Each signer must be unique, otherwise one key could satisfy the threshold alone
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            !signers[i + 1..].contains(signer),
+            FactoryError::DuplicateSigner
+        );
+    }
+
     let multisig = &mut ctx.accounts.multisig;
     multisig.token_mint = ctx.accounts.token_mint.key();
     multisig.signers = signers;
@@ -47,11 +58,19 @@ pub fn create_transaction(
         FactoryError::NotASigner
     );
 
+    let clock = Clock::get()?;
+
+    // Reject deadlines that are already in the past
+    if let Some(deadline) = deadline {
+        require!(
+            deadline > clock.unix_timestamp,
+            FactoryError::InvalidDeadline
+        );
+    }
+
     let transaction_id = multisig.transaction_count;
     multisig.transaction_count += 1;
 
-    let clock = Clock::get()?;
-
     transaction.multisig = multisig.key();
     transaction.transaction_id = transaction_id;
     transaction.transaction_type = transaction_type;
@@ -107,6 +126,50 @@ pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> Result<()> {
     Ok(())
 }
 
+/// Withdraw a mistaken approval before execution. The proposer cannot revoke
+/// the last remaining approval (cancel instead); if the proposer revokes while
+/// others remain, the earliest remaining approver takes over cancel rights.
+pub fn revoke_approval(ctx: Context<RevokeApproval>) -> Result<()> {
+    let transaction = &mut ctx.accounts.transaction;
+    let approver = ctx.accounts.approver.key();
+
+    // Verify not already executed
+    require!(!transaction.executed, FactoryError::AlreadyExecuted);
+
+    let position = transaction.approvers
+        .iter()
+        .position(|a| *a == approver)
+        .ok_or(FactoryError::NotApproved)?;
+
+    // The proposer holds the first approval; only they are stopped from revoking the last one
+    let is_proposer = position == 0;
+    if is_proposer {
+        require!(
+            transaction.approvers.len() > 1,
+            FactoryError::CannotRevokeLastApproval
+        );
+    }
+
+    transaction.approvers.remove(position);
+
+    emit!(ApprovalRevoked {
+        multisig: ctx.accounts.multisig.key(),
+        transaction: transaction.key(),
+        transaction_id: transaction.transaction_id,
+        revoked_by: approver,
+        remaining_approvals: transaction.approvers.len() as u8,
+        slot: Clock::get()?.slot,
+    });
+
+    msg!("Transaction {} approval revoked by {} ({}/{})",
+        transaction.transaction_id,
+        approver,
+        transaction.approvers.len(),
+        ctx.accounts.multisig.threshold);
+
+    Ok(())
+}
+
 /// Execute a multi-sig transaction after reaching threshold
 pub fn execute_transaction(ctx: Context<ExecuteTransaction>) -> Result<()> {
     let multisig = &ctx.accounts.multisig;
@@ -171,6 +234,38 @@ pub fn cancel_transaction(ctx: Context<CancelTransaction>) -> Result<()> {
     Ok(())
 }
 
+/// Read-only: returns the approval state of a transaction for `signer` via return data.
+/// Mirrors the checks in approve_transaction and execute_transaction.
+pub fn get_multisig_state(ctx: Context<GetMultiSigState>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let transaction = &ctx.accounts.transaction;
+    let signer = ctx.accounts.signer.key();
+
+    let expired = match transaction.deadline {
+        Some(deadline) => Clock::get()?.unix_timestamp > deadline,
+        None => false,
+    };
+    let has_approved = transaction.approvers.contains(&signer);
+    let can_approve = !transaction.executed
+        && !expired
+        && !has_approved
+        && multisig.signers.contains(&signer);
+
+    let state = MultiSigTransactionState {
+        threshold: multisig.threshold,
+        approval_count: transaction.approvers.len() as u8,
+        has_approved,
+        can_approve,
+        threshold_met: transaction.approvers.len() >= multisig.threshold as usize,
+        executed: transaction.executed,
+        expired,
+    };
+
+    set_return_data(&state.try_to_vec()?);
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct InitMultiSig<'info> {
     #[account(
@@ -244,6 +339,29 @@ pub struct ApproveTransaction<'info> {
     pub approver: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.token_mint.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultiSig>,
+
+    #[account(
+        mut,
+        seeds = [
+            TRANSACTION_SEED,
+            multisig.key().as_ref(),
+            transaction.transaction_id.to_le_bytes().as_ref()
+        ],
+        bump = transaction.bump,
+        constraint = transaction.multisig == multisig.key()
+    )]
+    pub transaction: Account<'info, MultiSigTransaction>,
+
+    pub approver: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct ExecuteTransaction<'info> {
     #[account(
@@ -291,3 +409,27 @@ pub struct CancelTransaction<'info> {
     #[account(mut)]
     pub canceller: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct GetMultiSigState<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.token_mint.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultiSig>,
+
+    #[account(
+        seeds = [
+            TRANSACTION_SEED,
+            multisig.key().as_ref(),
+            transaction.transaction_id.to_le_bytes().as_ref()
+        ],
+        bump = transaction.bump,
+        constraint = transaction.multisig == multisig.key()
+    )]
+    pub transaction: Account<'info, MultiSigTransaction>,
+
+    /// Signer whose approval status is queried
+    /// CHECK: Only the key is read
+    pub signer: UncheckedAccount<'info>,
+}
@@ -54,6 +54,23 @@ pub mod chainequity_factory {
         instructions::admin::transfer_mint_authority_handler(ctx)
     }
 
+    /// Get the token config address for a token ID (returned via return data).
+    /// Token IDs are sequential from 0 to token_count - 1.
+    pub fn get_token_config_address(
+        ctx: Context<GetTokenConfigAddress>,
+        token_id: u64,
+    ) -> Result<()> {
+        instructions::admin::get_token_config_address_handler(ctx, token_id)
+    }
+
+    /// Move a token to a new symbol, freeing the old one (symbols are unique per factory)
+    pub fn reassign_symbol(
+        ctx: Context<ReassignSymbol>,
+        new_symbol: String,
+    ) -> Result<()> {
+        instructions::admin::reassign_symbol_handler(ctx, new_symbol)
+    }
+
     // ============================================
     // Multi-Sig Instructions
     // ============================================
@@ -83,6 +100,13 @@ pub mod chainequity_factory {
         instructions::multisig::approve_transaction(ctx)
     }
 
+    /// Withdraw a previous approval from a pending multi-sig transaction
+    pub fn revoke_multisig_approval(
+        ctx: Context<RevokeApproval>,
+    ) -> Result<()> {
+        instructions::multisig::revoke_approval(ctx)
+    }
+
     /// Execute a multi-sig transaction after reaching threshold
     pub fn execute_multisig_transaction(
         ctx: Context<ExecuteTransaction>,
@@ -96,4 +120,11 @@ pub mod chainequity_factory {
     ) -> Result<()> {
         instructions::multisig::cancel_transaction(ctx)
     }
+
+    /// Get the approval state of a multi-sig transaction for a signer (returned via return data)
+    pub fn get_multisig_state(
+        ctx: Context<GetMultiSigState>,
+    ) -> Result<()> {
+        instructions::multisig::get_multisig_state(ctx)
+    }
 }
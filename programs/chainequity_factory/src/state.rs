@@ -6,7 +6,9 @@ use anchor_lang::prelude::*;
 pub struct TokenFactory {
     /// Platform admin (can be multi-sig)
     pub authority: Pubkey,
-    /// Total tokens created (for sequential IDs)
+    /// Total tokens created (for sequential IDs). Token configs are PDAs derived from
+    /// (factory, token_id) for every token_id < token_count, so this also serves as the
+    /// registry size. IDs only ever increase and are never reused.
     pub token_count: u64,
     /// Fee to create token (0 for demo)
     pub creation_fee: u64,
@@ -28,6 +30,28 @@ impl TokenFactory {
         1;   // bump
 }
 
+/// Claims a ticker within a factory. One PDA per (factory, symbol); its existence
+/// means the symbol is taken.
+#[account]
+pub struct SymbolRegistry {
+    /// Factory the symbol is unique within
+    pub factory: Pubkey,
+    /// Token that holds the symbol
+    pub token_config: Pubkey,
+    /// The claimed symbol (max 10 chars)
+    pub symbol: String,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SymbolRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // factory
+        32 + // token_config
+        (4 + 10) + // symbol
+        1;   // bump
+}
+
 /// Template for quick token creation with preset configurations
 #[account]
 pub struct TokenTemplate {
@@ -131,6 +155,8 @@ pub struct CreateTokenParams {
     pub admin_signers: Vec<Pubkey>,
     /// Required signatures
     pub admin_threshold: u8,
+    /// Treasury wallet for forfeited vesting and other returned shares
+    pub treasury: Pubkey,
     /// Optional template to use
     pub template_id: Option<u8>,
 }
@@ -160,6 +186,8 @@ pub const TEMPLATE_SEED: &[u8] = b"template";
 pub const MULTISIG_SEED: &[u8] = b"multisig";
 /// Seeds for transaction PDA
 pub const TRANSACTION_SEED: &[u8] = b"transaction";
+/// Seeds for symbol registry PDA
+pub const SYMBOL_SEED: &[u8] = b"symbol";
 
 /// Multi-sig wallet configuration for admin operations
 #[account]
@@ -220,6 +248,26 @@ impl MultiSigTransaction {
         1;   // bump
 }
 
+/// Approval status of a multi-sig transaction as seen by one signer
+/// (returned by `get_multisig_state`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MultiSigTransactionState {
+    /// Required number of signatures
+    pub threshold: u8,
+    /// Current number of approvals
+    pub approval_count: u8,
+    /// Whether the queried signer has already approved
+    pub has_approved: bool,
+    /// Whether the queried signer may still approve (is a signer, not yet approved, pending, not expired)
+    pub can_approve: bool,
+    /// Whether the threshold has been reached
+    pub threshold_met: bool,
+    /// Whether the transaction has already been executed
+    pub executed: bool,
+    /// Whether the deadline has passed
+    pub expired: bool,
+}
+
 /// Types of multi-sig transactions
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum TransactionType {
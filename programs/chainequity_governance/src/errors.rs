@@ -32,9 +32,27 @@ pub enum GovernanceError {
     #[msg("Cannot cancel after voting started")]
     VotingAlreadyStarted,
 
-    #[msg("Description too long (max 500 characters)")]
+    #[msg("Only cancelled proposals can be closed")]
+    ProposalNotCancelled,
+
+    #[msg("Description too long (max 500 characters, or 64 for a title with a description hash)")]
     DescriptionTooLong,
 
+    #[msg("Description is shorter than the governance minimum")]
+    DescriptionTooShort,
+
+    #[msg("Proposer must wait for the proposal cooldown to elapse")]
+    ProposalCooldownActive,
+
+    #[msg("Emergency proposals are disabled or not allowed for this action")]
+    EmergencyNotAllowed,
+
+    #[msg("Proposer holds too few tokens for an emergency proposal")]
+    EmergencyThresholdNotMet,
+
+    #[msg("Description hash must be a non-zero 32-byte content hash")]
+    InvalidDescriptionHash,
+
     #[msg("Feature not enabled for this token")]
     FeatureDisabled,
 
@@ -46,4 +64,28 @@ pub enum GovernanceError {
 
     #[msg("Invalid token config")]
     InvalidTokenConfig,
+
+    #[msg("Executed action has already been consumed")]
+    ActionAlreadyConsumed,
+
+    #[msg("Only the proposal executor can consume its action marker")]
+    NotExecutor,
+
+    #[msg("Invalid governance action parameters")]
+    InvalidAction,
+
+    #[msg("Invalid governance configuration")]
+    InvalidConfig,
+
+    #[msg("Executor is not authorized to execute this proposal")]
+    Unauthorized,
+
+    #[msg("Governed token has been retired")]
+    TokenNotActive,
+
+    #[msg("Governed token is paused")]
+    TokenPaused,
+
+    #[msg("Symbol registry accounts and the factory program are required to update the symbol")]
+    SymbolAccountsRequired,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{GovernanceAction, Vote, ProposalStatus};
+use crate::state::{GovernanceAction, Vote, ProposalStatus, ApprovalBasis};
 
 #[event]
 pub struct GovernanceConfigInitialized {
@@ -9,7 +9,16 @@ pub struct GovernanceConfigInitialized {
     pub voting_delay: u64,
     pub voting_period: u64,
     pub quorum_percentage: u8,
+    pub quorum_absolute: Option<u64>,
     pub approval_threshold: u8,
+    pub approval_basis: ApprovalBasis,
+    pub max_voting_power_bps: Option<u16>,
+    pub execution_restricted: bool,
+    pub execute_when_paused: bool,
+    pub proposal_cooldown: u64,
+    pub min_description_len: u16,
+    pub emergency_voting_period: u64,
+    pub emergency_proposal_threshold: u64,
     pub slot: u64,
 }
 
@@ -20,6 +29,9 @@ pub struct ProposalCreated {
     pub proposal_id: u64,
     pub proposer: Pubkey,
     pub action: GovernanceAction,
+    /// Content hash of the off-chain description, if not stored inline
+    pub description_hash: Option<[u8; 32]>,
+    pub emergency: bool,
     pub voting_starts: i64,
     pub voting_ends: i64,
     pub snapshot_slot: u64,
@@ -28,6 +40,16 @@ pub struct ProposalCreated {
 
 #[event]
 pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub vote: Vote,
+    /// Effective weight after any voting power cap
+    pub weight: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VoteWithdrawn {
     pub proposal: Pubkey,
     pub voter: Pubkey,
     pub vote: Vote,
@@ -40,6 +62,8 @@ pub struct ProposalStatusChanged {
     pub proposal: Pubkey,
     pub old_status: ProposalStatus,
     pub new_status: ProposalStatus,
+    /// Distinct voters at the time of the change
+    pub voter_count: u64,
     pub slot: u64,
 }
 
@@ -57,6 +81,14 @@ pub struct ProposalCancelled {
     pub slot: u64,
 }
 
+#[event]
+pub struct ProposalClosed {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub closed_by: Pubkey,
+    pub slot: u64,
+}
+
 // ============================================================================
 // GOVERNANCE ACTION EXECUTION EVENTS
 // ============================================================================
@@ -80,12 +112,40 @@ pub struct SymbolChangeInitiated {
     pub slot: u64,
 }
 
+#[event]
+pub struct DefaultRestrictionsUpdated {
+    pub token_config: Pubkey,
+    pub proposal: Pubkey,
+    pub daily_limit: Option<u64>,
+    pub lockout_until: Option<i64>,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TokenPausedByGovernance {
+    pub token_config: Pubkey,
+    pub proposal: Pubkey,
+    pub paused: bool,
+    pub executed_by: Pubkey,
+    pub slot: u64,
+}
+
 #[event]
 pub struct DividendInitiated {
     pub token_config: Pubkey,
     pub proposal: Pubkey,
     pub payment_token: Pubkey,
     pub total_amount: u64,
+    pub round_id: u64,
     pub initiated_by: Pubkey,
     pub slot: u64,
 }
+
+#[event]
+pub struct ActionMarkerConsumed {
+    pub proposal: Pubkey,
+    pub marker: Pubkey,
+    pub consumed_by: Pubkey,
+    pub slot: u64,
+}
@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::state::{Proposal, ProposalStatus, PROPOSAL_SEED};
+use crate::state::{GovernanceConfig, Proposal, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED};
 use crate::errors::GovernanceError;
-use crate::events::{ProposalCancelled, ProposalStatusChanged};
+use crate::events::{ProposalCancelled, ProposalClosed, ProposalStatusChanged};
 
 #[derive(Accounts)]
 pub struct CancelProposal<'info> {
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED, proposal.token_config.as_ref()],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
     #[account(
         mut,
-        seeds = [PROPOSAL_SEED, proposal.token_config.as_ref(), &proposal.id.to_le_bytes()],
+        seeds = [PROPOSAL_SEED, governance_config.key().as_ref(), &proposal.id.to_le_bytes()],
         bump = proposal.bump,
         constraint = proposal.proposer == proposer.key() @ GovernanceError::NotProposer,
     )]
@@ -33,6 +39,7 @@ pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
         proposal: proposal.key(),
         old_status,
         new_status: ProposalStatus::Cancelled,
+        voter_count: proposal.voter_count,
         slot: clock.slot,
     });
 
@@ -46,3 +53,43 @@ pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct CloseCancelledProposal<'info> {
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED, governance_config.token_config.as_ref()],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, governance_config.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.proposer == proposer.key() @ GovernanceError::NotProposer,
+        constraint = proposal.status == ProposalStatus::Cancelled @ GovernanceError::ProposalNotCancelled,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+/// Close a cancelled proposal and refund its rent to the proposer.
+/// Proposal IDs are never reused, so closing the account is safe.
+pub fn close_handler(ctx: Context<CloseCancelledProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &ctx.accounts.proposal;
+
+    emit!(ProposalClosed {
+        proposal: proposal.key(),
+        proposal_id: proposal.id,
+        closed_by: ctx.accounts.proposer.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Closed cancelled proposal {}", proposal.id);
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::state::{GovernanceConfig, Proposal, GovernanceAction, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED};
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{GovernanceConfig, Proposal, ProposerRecord, GovernanceAction, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED, PROPOSER_RECORD_SEED};
 use crate::errors::GovernanceError;
 use crate::events::ProposalCreated;
 
+use chainequity_factory::instructions::create_token::TokenConfig;
+
 #[derive(Accounts)]
-#[instruction(action: GovernanceAction, description: String)]
+#[instruction(action: GovernanceAction, description: String, description_hash: Option<[u8; 32]>)]
 pub struct CreateProposal<'info> {
     #[account(
         mut,
@@ -16,7 +19,7 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = Proposal::LEN,
+        space = Proposal::space(description_hash.is_some()),
         seeds = [
             PROPOSAL_SEED,
             governance_config.key().as_ref(),
@@ -26,6 +29,28 @@ pub struct CreateProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// Tracks the proposer's last proposal for the cooldown
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = ProposerRecord::LEN,
+        seeds = [PROPOSER_RECORD_SEED, governance_config.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub proposer_record: Account<'info, ProposerRecord>,
+
+    /// Token config - required for emergency proposals to check the proposer's balance
+    #[account(
+        constraint = token_config.key() == governance_config.token_config @ GovernanceError::InvalidTokenConfig,
+    )]
+    pub token_config: Option<Box<Account<'info, TokenConfig>>>,
+
+    /// Proposer's token account - required for emergency proposals
+    #[account(
+        token::authority = proposer,
+    )]
+    pub proposer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(mut)]
     pub proposer: Signer<'info>,
 
@@ -36,13 +61,61 @@ pub fn handler(
     ctx: Context<CreateProposal>,
     action: GovernanceAction,
     description: String,
+    description_hash: Option<[u8; 32]>,
+    emergency: bool,
 ) -> Result<()> {
-    require!(description.len() <= 500, GovernanceError::DescriptionTooLong);
+    // With an off-chain description, the inline text is only a short title
+    let max_len = match description_hash {
+        Some(_) => Proposal::MAX_TITLE_LEN,
+        None => Proposal::MAX_DESCRIPTION_LEN,
+    };
+    require!(description.len() <= max_len, GovernanceError::DescriptionTooLong);
+    // A hashed description carries its rationale off-chain; inline text must give some context
+    if description_hash.is_none() {
+        require!(
+            description.len() >= ctx.accounts.governance_config.min_description_len as usize,
+            GovernanceError::DescriptionTooShort
+        );
+    }
+    if let Some(hash) = description_hash {
+        require!(hash != [0u8; 32], GovernanceError::InvalidDescriptionHash);
+    }
+    action.validate()?;
+
+    // Emergency proposals skip the voting delay, so they are limited to eligible
+    // actions and need a larger stake than routine proposals
+    if emergency {
+        let config = &ctx.accounts.governance_config;
+        require!(
+            config.emergency_voting_period > 0 && action.is_emergency_eligible(),
+            GovernanceError::EmergencyNotAllowed
+        );
+        let token_config = ctx.accounts.token_config.as_ref().ok_or(GovernanceError::InvalidTokenConfig)?;
+        let proposer_token_account = ctx.accounts.proposer_token_account
+            .as_ref()
+            .ok_or(GovernanceError::EmergencyThresholdNotMet)?;
+        require_keys_eq!(proposer_token_account.mint, token_config.mint, GovernanceError::InvalidTokenConfig);
+        require!(
+            proposer_token_account.amount >= config.emergency_proposal_threshold,
+            GovernanceError::EmergencyThresholdNotMet
+        );
+    }
 
     let clock = Clock::get()?;
     let config = &mut ctx.accounts.governance_config;
     let proposal = &mut ctx.accounts.proposal;
 
+    // Throttle repeat proposals from the same proposer (a fresh record has bump 0)
+    let record = &mut ctx.accounts.proposer_record;
+    if record.bump != 0 && config.proposal_cooldown > 0 {
+        let elapsed = clock.unix_timestamp.saturating_sub(record.last_proposal_ts);
+        require!(elapsed >= config.proposal_cooldown as i64, GovernanceError::ProposalCooldownActive);
+    }
+    record.governance_config = config.key();
+    record.proposer = ctx.accounts.proposer.key();
+    record.last_proposal_ts = clock.unix_timestamp;
+    record.bump = ctx.bumps.proposer_record;
+
     let proposal_id = config.proposal_count;
     config.proposal_count = config.proposal_count.checked_add(1).unwrap();
 
@@ -51,12 +124,21 @@ pub fn handler(
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.action = action.clone();
     proposal.description = description;
+    proposal.description_is_hash = description_hash.is_some();
+    proposal.description_hash = description_hash.unwrap_or_default();
     proposal.votes_for = 0;
     proposal.votes_against = 0;
     proposal.votes_abstain = 0;
+    proposal.voter_count = 0;
+    proposal.emergency = emergency;
     proposal.status = ProposalStatus::Pending;
-    proposal.voting_starts = clock.unix_timestamp + config.voting_delay as i64;
-    proposal.voting_ends = proposal.voting_starts + config.voting_period as i64;
+    if emergency {
+        proposal.voting_starts = clock.unix_timestamp;
+        proposal.voting_ends = proposal.voting_starts + config.emergency_voting_period as i64;
+    } else {
+        proposal.voting_starts = clock.unix_timestamp + config.voting_delay as i64;
+        proposal.voting_ends = proposal.voting_starts + config.voting_period as i64;
+    }
     proposal.execution_delay = config.execution_delay;
     proposal.executed_at = None;
     proposal.snapshot_slot = clock.slot;
@@ -68,6 +150,8 @@ pub fn handler(
         proposal_id,
         proposer: ctx.accounts.proposer.key(),
         action,
+        description_hash,
+        emergency,
         voting_starts: proposal.voting_starts,
         voting_ends: proposal.voting_ends,
         snapshot_slot: clock.slot,
@@ -1,14 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::{GovernanceConfig, Proposal, GovernanceAction, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED};
+use crate::state::{GovernanceConfig, Proposal, GovernanceAction, ProposalStatus, ExecutedActionMarker, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED, ACTION_MARKER_SEED};
 use crate::errors::GovernanceError;
-use crate::events::{ProposalExecuted, ProposalStatusChanged, StockSplitInitiated, SymbolChangeInitiated, DividendInitiated};
+use crate::events::{ProposalExecuted, ProposalStatusChanged, StockSplitInitiated, SymbolChangeInitiated, DividendInitiated, DefaultRestrictionsUpdated, TokenPausedByGovernance, ActionMarkerConsumed};
 
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
+use chainequity_factory::program::ChainequityFactory;
 
 /// Execute a passed proposal
-/// For stock splits and symbol changes, this marks the proposal as executed
-/// and emits events that the backend will process to complete the action.
+/// For stock splits, this marks the proposal as executed and emits events
+/// that the backend will process to complete the action.
+/// Symbol changes go through the factory's symbol registry via CPI.
 /// For dividends, this creates the dividend round on-chain.
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
@@ -25,6 +27,16 @@ pub struct ExecuteProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// Idempotency marker - can only be created once per proposal
+    #[account(
+        init,
+        payer = executor,
+        space = ExecutedActionMarker::LEN,
+        seeds = [ACTION_MARKER_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub action_marker: Account<'info, ExecutedActionMarker>,
+
     /// Token config for the token being governed
     #[account(
         mut,
@@ -38,6 +50,22 @@ pub struct ExecuteProposal<'info> {
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Token admin multisig - required when execution is restricted
+    #[account(
+        constraint = multisig.key() == token_config.authority @ GovernanceError::Unauthorized,
+    )]
+    pub multisig: Option<Account<'info, MultisigConfig>>,
+
+    /// CHECK: Registry entry for the current symbol, validated and closed by the factory (UpdateSymbol only)
+    #[account(mut)]
+    pub old_symbol_registry: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Registry entry for the new symbol, validated and claimed by the factory (UpdateSymbol only)
+    #[account(mut)]
+    pub new_symbol_registry: Option<UncheckedAccount<'info>>,
+
+    pub factory_program: Option<Program<'info, ChainequityFactory>>,
+
     #[account(mut)]
     pub executor: Signer<'info>,
 
@@ -54,6 +82,25 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
     // Check proposal has passed
     require!(proposal.status == ProposalStatus::Passed, GovernanceError::ProposalNotPassed);
 
+    // Restricted execution: executor must be a multisig signer
+    if config.execution_restricted {
+        let multisig = ctx.accounts.multisig.as_ref().ok_or(GovernanceError::Unauthorized)?;
+        require!(
+            multisig.signers.contains(&ctx.accounts.executor.key()),
+            GovernanceError::Unauthorized
+        );
+    }
+
+    // Never act on a retired token; pause blocks execution unless configured otherwise
+    // (SetPaused is always allowed so governance can lift a pause)
+    require!(token_config.is_active, GovernanceError::TokenNotActive);
+    require!(
+        !token_config.is_paused
+            || config.execute_when_paused
+            || matches!(proposal.action, GovernanceAction::SetPaused { .. }),
+        GovernanceError::TokenPaused
+    );
+
     // Check not already executed
     require!(proposal.executed_at.is_none(), GovernanceError::AlreadyExecuted);
 
@@ -65,6 +112,9 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
     let execution_window_ends = execution_allowed_at + config.execution_window as i64;
     require!(clock.unix_timestamp <= execution_window_ends, GovernanceError::ExecutionWindowPassed);
 
+    // Re-check parameters in case the proposal predates validation
+    proposal.action.validate()?;
+
     // Execute the action based on type
     match &proposal.action {
         GovernanceAction::InitiateStockSplit { multiplier } => {
@@ -81,9 +131,32 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
             msg!("Stock split initiated: {}x multiplier", multiplier);
         }
         GovernanceAction::UpdateSymbol { new_symbol } => {
-            // Update the symbol directly in token_config
+            // The factory owns the symbol registry and enforces uniqueness; it also
+            // requires the executor to be a token multisig signer
+            let (Some(multisig), Some(old_symbol_registry), Some(new_symbol_registry), Some(factory_program)) = (
+                ctx.accounts.multisig.as_ref(),
+                ctx.accounts.old_symbol_registry.as_ref(),
+                ctx.accounts.new_symbol_registry.as_ref(),
+                ctx.accounts.factory_program.as_ref(),
+            ) else {
+                return Err(GovernanceError::SymbolAccountsRequired.into());
+            };
+
             let old_symbol = token_config.symbol.clone();
-            token_config.symbol = new_symbol.clone();
+            chainequity_factory::cpi::reassign_symbol(
+                CpiContext::new(
+                    factory_program.to_account_info(),
+                    chainequity_factory::cpi::accounts::ReassignSymbol {
+                        token_config: token_config.to_account_info(),
+                        multisig: multisig.to_account_info(),
+                        old_symbol_registry: old_symbol_registry.to_account_info(),
+                        new_symbol_registry: new_symbol_registry.to_account_info(),
+                        authority: ctx.accounts.executor.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                ),
+                new_symbol.clone(),
+            )?;
 
             emit!(SymbolChangeInitiated {
                 token_config: token_config.key(),
@@ -95,30 +168,66 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
             });
             msg!("Symbol changed to: {}", new_symbol);
         }
-        GovernanceAction::InitiateDividend { payment_token, total_amount } => {
+        GovernanceAction::InitiateDividend { payment_token, total_amount, round_id } => {
             // Emit event for backend to create the dividend round
             // The dividend round creation requires additional accounts
-            // (payment token vault, etc.) that would need a separate instruction
+            // (payment token vault, etc.), so the executor calls create_dividend_round,
+            // which consumes this proposal's action marker
             emit!(DividendInitiated {
                 token_config: token_config.key(),
                 proposal: proposal.key(),
                 payment_token: *payment_token,
                 total_amount: *total_amount,
+                round_id: *round_id,
                 initiated_by: ctx.accounts.executor.key(),
                 slot: clock.slot,
             });
             msg!("Dividend initiated: {} tokens from {:?}", total_amount, payment_token);
         }
+        GovernanceAction::SetDefaultRestrictions { daily_limit, lockout_until } => {
+            token_config.default_daily_limit = *daily_limit;
+            token_config.default_lockout_until = *lockout_until;
+
+            emit!(DefaultRestrictionsUpdated {
+                token_config: token_config.key(),
+                proposal: proposal.key(),
+                daily_limit: *daily_limit,
+                lockout_until: *lockout_until,
+                updated_by: ctx.accounts.executor.key(),
+                slot: clock.slot,
+            });
+            msg!("Default restrictions set: daily_limit={:?}, lockout_until={:?}", daily_limit, lockout_until);
+        }
+        GovernanceAction::SetPaused { paused } => {
+            token_config.is_paused = *paused;
+
+            emit!(TokenPausedByGovernance {
+                token_config: token_config.key(),
+                proposal: proposal.key(),
+                paused: *paused,
+                executed_by: ctx.accounts.executor.key(),
+                slot: clock.slot,
+            });
+            msg!("Token paused state set to: {}", paused);
+        }
     }
 
     let old_status = proposal.status.clone();
     proposal.status = ProposalStatus::Executed;
     proposal.executed_at = Some(clock.unix_timestamp);
 
+    let marker = &mut ctx.accounts.action_marker;
+    marker.proposal = proposal.key();
+    marker.executed_by = ctx.accounts.executor.key();
+    marker.consumed = false;
+    marker.created_at = clock.unix_timestamp;
+    marker.bump = ctx.bumps.action_marker;
+
     emit!(ProposalStatusChanged {
         proposal: proposal.key(),
         old_status,
         new_status: ProposalStatus::Executed,
+        voter_count: proposal.voter_count,
         slot: clock.slot,
     });
 
@@ -132,3 +241,42 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
 
     Ok(())
 }
+
+/// Consume the action marker of an executed proposal. Downstream processing of the
+/// action includes this in the same transaction so it can happen at most once;
+/// chainequity_token's create_dividend_round invokes it for governed tokens.
+#[derive(Accounts)]
+pub struct ConsumeActionMarker<'info> {
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ GovernanceError::ProposalNotPassed,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [ACTION_MARKER_SEED, proposal.key().as_ref()],
+        bump = action_marker.bump,
+        constraint = !action_marker.consumed @ GovernanceError::ActionAlreadyConsumed,
+        constraint = action_marker.executed_by == authority.key() @ GovernanceError::NotExecutor,
+    )]
+    pub action_marker: Account<'info, ExecutedActionMarker>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn consume_marker_handler(ctx: Context<ConsumeActionMarker>) -> Result<()> {
+    let clock = Clock::get()?;
+    let marker = &mut ctx.accounts.action_marker;
+    marker.consumed = true;
+
+    emit!(ActionMarkerConsumed {
+        proposal: ctx.accounts.proposal.key(),
+        marker: marker.key(),
+        consumed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Consumed action marker for proposal {}", ctx.accounts.proposal.id);
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::state::{GovernanceConfig, Proposal, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED};
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::{GovernanceConfig, Proposal, ProposalStatus, ApprovalBasis, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED};
 use crate::errors::GovernanceError;
 use crate::events::ProposalStatusChanged;
 
+use chainequity_factory::instructions::create_token::TokenConfig;
+
 #[derive(Accounts)]
 pub struct FinalizeProposal<'info> {
     #[account(
@@ -17,9 +20,15 @@ pub struct FinalizeProposal<'info> {
         bump = proposal.bump,
     )]
     pub proposal: Account<'info, Proposal>,
+
+    /// Token being governed - supply is read on-chain rather than trusted from the caller
+    #[account(
+        constraint = token_config.key() == governance_config.token_config @ GovernanceError::InvalidTokenConfig,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
 }
 
-pub fn finalize_handler(ctx: Context<FinalizeProposal>, total_supply: u64) -> Result<()> {
+pub fn finalize_handler(ctx: Context<FinalizeProposal>) -> Result<()> {
     let clock = Clock::get()?;
     let config = &ctx.accounts.governance_config;
     let proposal = &mut ctx.accounts.proposal;
@@ -27,6 +36,15 @@ pub fn finalize_handler(ctx: Context<FinalizeProposal>, total_supply: u64) -> Re
     // Check voting has ended
     require!(clock.unix_timestamp > proposal.voting_ends, GovernanceError::VotingNotStarted);
 
+    // Already finalized (e.g. a bot and a user racing): succeed without changing state
+    if matches!(
+        proposal.status,
+        ProposalStatus::Passed | ProposalStatus::Failed | ProposalStatus::Executed
+    ) {
+        msg!("Proposal {} already finalized as {:?}", proposal.id, proposal.status);
+        return Ok(());
+    }
+
     // Can only finalize active or pending proposals
     require!(
         proposal.status == ProposalStatus::Active || proposal.status == ProposalStatus::Pending,
@@ -35,19 +53,8 @@ pub fn finalize_handler(ctx: Context<FinalizeProposal>, total_supply: u64) -> Re
 
     let old_status = proposal.status.clone();
 
-    // Calculate if quorum was reached
-    let total_votes = proposal.votes_for + proposal.votes_against + proposal.votes_abstain;
-    let quorum_threshold = (total_supply as u128 * config.quorum_percentage as u128 / 100) as u64;
-    let quorum_reached = total_votes >= quorum_threshold;
-
-    // Calculate if approval threshold was reached
-    let decisive_votes = proposal.votes_for + proposal.votes_against;
-    let approval_reached = if decisive_votes > 0 {
-        let approval_pct = (proposal.votes_for as u128 * 100) / decisive_votes as u128;
-        approval_pct >= config.approval_threshold as u128
-    } else {
-        false
-    };
+    let total_supply = ctx.accounts.token_config.total_supply;
+    let (quorum_reached, approval_reached) = compute_result(config, proposal, total_supply);
 
     // Determine final status
     if quorum_reached && approval_reached {
@@ -60,6 +67,7 @@ pub fn finalize_handler(ctx: Context<FinalizeProposal>, total_supply: u64) -> Re
         proposal: proposal.key(),
         old_status,
         new_status: proposal.status.clone(),
+        voter_count: proposal.voter_count,
         slot: clock.slot,
     });
 
@@ -73,3 +81,63 @@ pub fn finalize_handler(ctx: Context<FinalizeProposal>, total_supply: u64) -> Re
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct PreviewProposalResult<'info> {
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED, governance_config.token_config.as_ref()],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    #[account(
+        seeds = [PROPOSAL_SEED, governance_config.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Token being governed - supply is read on-chain rather than trusted from the caller
+    #[account(
+        constraint = token_config.key() == governance_config.token_config @ GovernanceError::InvalidTokenConfig,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+}
+
+/// Read-only: returns (quorum_reached, approval_reached) via return data without finalizing
+pub fn preview_handler(ctx: Context<PreviewProposalResult>) -> Result<()> {
+    let (quorum_reached, approval_reached) = compute_result(
+        &ctx.accounts.governance_config,
+        &ctx.accounts.proposal,
+        ctx.accounts.token_config.total_supply,
+    );
+
+    set_return_data(&[quorum_reached as u8, approval_reached as u8]);
+
+    Ok(())
+}
+
+/// Compute (quorum_reached, approval_reached) for a proposal's current tally
+pub fn compute_result(config: &GovernanceConfig, proposal: &Proposal, total_supply: u64) -> (bool, bool) {
+    // Calculate if quorum was reached
+    let total_votes = proposal.votes_for as u128 + proposal.votes_against as u128 + proposal.votes_abstain as u128;
+    // An absolute quorum does not depend on total_supply at all
+    let quorum_threshold = match config.quorum_absolute {
+        Some(quorum_absolute) => quorum_absolute as u128,
+        None => total_supply as u128 * config.quorum_percentage as u128 / 100,
+    };
+    let quorum_reached = total_votes >= quorum_threshold;
+
+    // Calculate if approval threshold was reached, against votes cast or total supply
+    let approval_base = match config.approval_basis {
+        ApprovalBasis::OfCast => proposal.votes_for as u128 + proposal.votes_against as u128,
+        ApprovalBasis::OfSupply => total_supply as u128,
+    };
+    let approval_reached = if approval_base > 0 {
+        let approval_pct = (proposal.votes_for as u128 * 100) / approval_base;
+        approval_pct >= config.approval_threshold as u128
+    } else {
+        false
+    };
+
+    (quorum_reached, approval_reached)
+}
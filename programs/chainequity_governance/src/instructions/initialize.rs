@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{GovernanceConfig, GOVERNANCE_CONFIG_SEED};
+use crate::state::{GovernanceConfig, ApprovalBasis, Proposal, GOVERNANCE_CONFIG_SEED};
+use crate::errors::GovernanceError;
 use crate::events::GovernanceConfigInitialized;
 
 #[derive(Accounts)]
@@ -31,17 +32,60 @@ pub struct InitializeGovernanceParams {
     pub voting_delay: u64,
     /// Seconds voting is open (e.g., 259200 = 3 days)
     pub voting_period: u64,
-    /// Percentage of supply that must vote (e.g., 10 = 10%)
+    /// Percentage of supply that must vote (e.g., 10 = 10%); must be 0 when quorum_absolute is set
     pub quorum_percentage: u8,
+    /// Absolute token count that must vote, instead of a percentage of supply
+    pub quorum_absolute: Option<u64>,
     /// Percentage of votes needed to pass (e.g., 50 = 50%)
     pub approval_threshold: u8,
+    /// Whether approval_threshold is measured against votes cast or total supply
+    pub approval_basis: ApprovalBasis,
+    /// Optional cap on a single voter's weight in basis points of supply (e.g., 1000 = 10%)
+    pub max_voting_power_bps: Option<u16>,
+    /// Restrict execution to the token's multisig signers (false = permissionless)
+    pub execution_restricted: bool,
+    /// Allow executing passed proposals while the token is paused
+    pub execute_when_paused: bool,
     /// Seconds after passing before execution allowed (e.g., 86400 = 1 day)
     pub execution_delay: u64,
     /// Seconds window to execute after delay (e.g., 604800 = 7 days)
     pub execution_window: u64,
+    /// Minimum seconds between proposals from the same proposer (0 = no cooldown)
+    pub proposal_cooldown: u64,
+    /// Minimum inline description length (e.g., 10); 0 disables the check
+    pub min_description_len: u16,
+    /// Voting period for emergency proposals (0 = emergency proposals disabled)
+    pub emergency_voting_period: u64,
+    /// Tokens required to create an emergency proposal (must exceed min_proposal_threshold)
+    pub emergency_proposal_threshold: u64,
 }
 
 pub fn initialize_handler(ctx: Context<InitializeGovernance>, params: InitializeGovernanceParams) -> Result<()> {
+    // Reject configs under which no proposal could ever pass or execute
+    require!(params.voting_period > 0, GovernanceError::InvalidConfig);
+    require!(params.execution_window > 0, GovernanceError::InvalidConfig);
+    require!(params.quorum_percentage <= 100, GovernanceError::InvalidConfig);
+    // Exactly one quorum mode: an absolute quorum replaces the percentage
+    if let Some(quorum_absolute) = params.quorum_absolute {
+        require!(quorum_absolute > 0, GovernanceError::InvalidConfig);
+        require!(params.quorum_percentage == 0, GovernanceError::InvalidConfig);
+    }
+    require!(params.approval_threshold <= 100, GovernanceError::InvalidConfig);
+    if let Some(bps) = params.max_voting_power_bps {
+        require!(bps > 0 && bps <= 10_000, GovernanceError::InvalidConfig);
+    }
+    require!(
+        params.min_description_len as usize <= Proposal::MAX_DESCRIPTION_LEN,
+        GovernanceError::InvalidConfig
+    );
+    if params.emergency_voting_period > 0 {
+        require!(params.emergency_voting_period <= params.voting_period, GovernanceError::InvalidConfig);
+        require!(
+            params.emergency_proposal_threshold > params.min_proposal_threshold,
+            GovernanceError::InvalidConfig
+        );
+    }
+
     let config = &mut ctx.accounts.governance_config;
     let clock = Clock::get()?;
 
@@ -50,9 +94,18 @@ pub fn initialize_handler(ctx: Context<InitializeGovernance>, params: Initialize
     config.voting_delay = params.voting_delay;
     config.voting_period = params.voting_period;
     config.quorum_percentage = params.quorum_percentage;
+    config.quorum_absolute = params.quorum_absolute;
     config.approval_threshold = params.approval_threshold;
+    config.approval_basis = params.approval_basis.clone();
+    config.max_voting_power_bps = params.max_voting_power_bps;
+    config.execution_restricted = params.execution_restricted;
+    config.execute_when_paused = params.execute_when_paused;
     config.execution_delay = params.execution_delay;
     config.execution_window = params.execution_window;
+    config.proposal_cooldown = params.proposal_cooldown;
+    config.min_description_len = params.min_description_len;
+    config.emergency_voting_period = params.emergency_voting_period;
+    config.emergency_proposal_threshold = params.emergency_proposal_threshold;
     config.proposal_count = 0;
     config.bump = ctx.bumps.governance_config;
 
@@ -63,7 +116,16 @@ pub fn initialize_handler(ctx: Context<InitializeGovernance>, params: Initialize
         voting_delay: params.voting_delay,
         voting_period: params.voting_period,
         quorum_percentage: params.quorum_percentage,
+        quorum_absolute: params.quorum_absolute,
         approval_threshold: params.approval_threshold,
+        approval_basis: params.approval_basis,
+        max_voting_power_bps: params.max_voting_power_bps,
+        execution_restricted: params.execution_restricted,
+        execute_when_paused: params.execute_when_paused,
+        proposal_cooldown: params.proposal_cooldown,
+        min_description_len: params.min_description_len,
+        emergency_voting_period: params.emergency_voting_period,
+        emergency_proposal_threshold: params.emergency_proposal_threshold,
         slot: clock.slot,
     });
 
@@ -1,14 +1,28 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::TokenAccount;
-use crate::state::{Proposal, VoteRecord, Vote, ProposalStatus, PROPOSAL_SEED, VOTE_RECORD_SEED};
+use crate::state::{GovernanceConfig, Proposal, VoteRecord, Vote, ProposalStatus, GOVERNANCE_CONFIG_SEED, PROPOSAL_SEED, VOTE_RECORD_SEED};
 use crate::errors::GovernanceError;
-use crate::events::VoteCast;
+use crate::events::{VoteCast, VoteWithdrawn};
+
+use chainequity_factory::instructions::create_token::TokenConfig;
 
 #[derive(Accounts)]
 pub struct CastVote<'info> {
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED, proposal.token_config.as_ref()],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    /// Token config - total supply bounds the voting power cap
+    #[account(
+        constraint = token_config.key() == governance_config.token_config @ GovernanceError::InvalidTokenConfig,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
     #[account(
         mut,
-        seeds = [PROPOSAL_SEED, proposal.token_config.as_ref(), &proposal.id.to_le_bytes()],
+        seeds = [PROPOSAL_SEED, governance_config.key().as_ref(), &proposal.id.to_le_bytes()],
         bump = proposal.bump,
     )]
     pub proposal: Account<'info, Proposal>,
@@ -23,8 +37,9 @@ pub struct CastVote<'info> {
     pub vote_record: Account<'info, VoteRecord>,
 
     /// Voter's token account - used to determine voting weight
-    /// The token account must be owned by the voter
+    /// The token account must hold this token and be owned by the voter
     #[account(
+        token::mint = token_config.mint,
         token::authority = voter,
     )]
     pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
@@ -53,8 +68,18 @@ pub fn handler(ctx: Context<CastVote>, vote: Vote) -> Result<()> {
     // Get voting weight from token balance
     // In a production system with historical snapshots, this would query the balance
     // at the proposal's snapshot_slot. For now, we use current balance.
-    let weight = ctx.accounts.voter_token_account.amount;
-    require!(weight > 0, GovernanceError::InsufficientTokens);
+    let balance = ctx.accounts.voter_token_account.amount;
+    require!(balance > 0, GovernanceError::InsufficientTokens);
+
+    // Apply the per-voter cap, if configured; at least one unit so a small supply
+    // can't round the cap down to zero and void every vote
+    let weight = match ctx.accounts.governance_config.max_voting_power_bps {
+        Some(bps) => {
+            let cap = (ctx.accounts.token_config.total_supply as u128 * bps as u128 / 10_000) as u64;
+            balance.min(cap.max(1))
+        }
+        None => balance,
+    };
 
     // Record vote
     match vote {
@@ -63,6 +88,9 @@ pub fn handler(ctx: Context<CastVote>, vote: Vote) -> Result<()> {
         Vote::Abstain => proposal.votes_abstain = proposal.votes_abstain.checked_add(weight).unwrap(),
     }
 
+    // VoteRecord is init-once per voter, so each call is a distinct voter
+    proposal.voter_count = proposal.voter_count.checked_add(1).unwrap();
+
     let vote_record = &mut ctx.accounts.vote_record;
     vote_record.proposal = proposal.key();
     vote_record.voter = ctx.accounts.voter.key();
@@ -83,3 +111,62 @@ pub fn handler(ctx: Context<CastVote>, vote: Vote) -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct WithdrawVote<'info> {
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED, proposal.token_config.as_ref()],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, governance_config.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        close = voter,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
+
+/// Remove a vote entirely while voting is open, reversing its tally and refunding
+/// the vote record's rent. The voter may cast a fresh vote afterwards.
+pub fn withdraw_handler(ctx: Context<WithdrawVote>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(clock.unix_timestamp <= proposal.voting_ends, GovernanceError::VotingEnded);
+    require!(proposal.status == ProposalStatus::Active, GovernanceError::ProposalNotActive);
+
+    let vote_record = &ctx.accounts.vote_record;
+    let weight = vote_record.weight;
+
+    match vote_record.vote {
+        Vote::For => proposal.votes_for = proposal.votes_for.saturating_sub(weight),
+        Vote::Against => proposal.votes_against = proposal.votes_against.saturating_sub(weight),
+        Vote::Abstain => proposal.votes_abstain = proposal.votes_abstain.saturating_sub(weight),
+    }
+    proposal.voter_count = proposal.voter_count.saturating_sub(1);
+
+    emit!(VoteWithdrawn {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        vote: vote_record.vote.clone(),
+        weight,
+        slot: clock.slot,
+    });
+
+    msg!("Vote withdrawn from proposal {} (weight {})", proposal.id, weight);
+
+    Ok(())
+}
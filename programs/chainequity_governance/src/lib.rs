@@ -22,13 +22,16 @@ pub mod chainequity_governance {
         instructions::initialize::initialize_handler(ctx, params)
     }
 
-    /// Create a governance proposal
+    /// Create a governance proposal. Pass description_hash to anchor the full text
+    /// off-chain and store only a short title (smaller account, less rent).
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         action: GovernanceAction,
         description: String,
+        description_hash: Option<[u8; 32]>,
+        emergency: bool,
     ) -> Result<()> {
-        instructions::create_proposal::handler(ctx, action, description)
+        instructions::create_proposal::handler(ctx, action, description, description_hash, emergency)
     }
 
     /// Cast a vote on a proposal
@@ -39,12 +42,21 @@ pub mod chainequity_governance {
         instructions::vote::handler(ctx, vote_choice)
     }
 
-    /// Finalize a proposal after voting ends (determine passed/failed)
-    pub fn finalize_proposal(
-        ctx: Context<FinalizeProposal>,
-        total_supply: u64,
-    ) -> Result<()> {
-        instructions::finalize::finalize_handler(ctx, total_supply)
+    /// Withdraw a vote while voting is open, removing its weight from the tallies
+    pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
+        instructions::vote::withdraw_handler(ctx)
+    }
+
+    /// Finalize a proposal after voting ends (determine passed/failed).
+    /// Calling it again on an already-finalized proposal is a no-op.
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize::finalize_handler(ctx)
+    }
+
+    /// Preview whether a proposal would pass without finalizing it.
+    /// Returns (quorum_reached, approval_reached) via return data.
+    pub fn preview_proposal_result(ctx: Context<PreviewProposalResult>) -> Result<()> {
+        instructions::finalize::preview_handler(ctx)
     }
 
     /// Execute a passed proposal
@@ -52,8 +64,18 @@ pub mod chainequity_governance {
         instructions::execute::handler(ctx)
     }
 
+    /// Consume an executed proposal's action marker (at most once)
+    pub fn consume_action_marker(ctx: Context<ConsumeActionMarker>) -> Result<()> {
+        instructions::execute::consume_marker_handler(ctx)
+    }
+
     /// Cancel a proposal (proposer only)
     pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
         instructions::cancel::handler(ctx)
     }
+
+    /// Close a cancelled proposal and refund rent (proposer only)
+    pub fn close_cancelled_proposal(ctx: Context<CloseCancelledProposal>) -> Result<()> {
+        instructions::cancel::close_handler(ctx)
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::GovernanceError;
 
 /// Governance configuration per token
 #[account]
@@ -13,12 +14,31 @@ pub struct GovernanceConfig {
     pub voting_period: u64,
     /// Percentage of supply that must vote (e.g., 10)
     pub quorum_percentage: u8,
+    /// Absolute number of tokens that must vote. When set, quorum_percentage is ignored;
+    /// exactly one of the two should be configured.
+    pub quorum_absolute: Option<u64>,
     /// Percentage of votes needed to pass (e.g., 66)
     pub approval_threshold: u8,
+    /// Whether approval_threshold applies to votes cast or to total supply
+    pub approval_basis: ApprovalBasis,
+    /// Cap on any single voter's weight, in basis points of total supply (None = no cap)
+    pub max_voting_power_bps: Option<u16>,
+    /// When true, only members of the token's admin multisig may execute passed proposals
+    pub execution_restricted: bool,
+    /// When true, passed proposals may still be executed while the token is paused
+    pub execute_when_paused: bool,
     /// Seconds after passing before execution allowed
     pub execution_delay: u64,
     /// Seconds window to execute after delay
     pub execution_window: u64,
+    /// Minimum seconds between proposals from the same proposer (0 = no cooldown)
+    pub proposal_cooldown: u64,
+    /// Minimum length of an inline proposal description (not applied to hashed descriptions)
+    pub min_description_len: u16,
+    /// Voting period for emergency proposals, which open immediately (0 = emergencies disabled)
+    pub emergency_voting_period: u64,
+    /// Tokens the proposer must hold to create an emergency proposal
+    pub emergency_proposal_threshold: u64,
     /// Total proposals created
     pub proposal_count: u64,
     /// PDA bump
@@ -32,13 +52,32 @@ impl GovernanceConfig {
         8 +  // voting_delay
         8 +  // voting_period
         1 +  // quorum_percentage
+        (1 + 8) + // quorum_absolute Option<u64>
         1 +  // approval_threshold
+        1 +  // approval_basis
+        (1 + 2) + // max_voting_power_bps Option<u16>
+        1 +  // execution_restricted
+        1 +  // execute_when_paused
         8 +  // execution_delay
         8 +  // execution_window
+        8 +  // proposal_cooldown
+        2 +  // min_description_len
+        8 +  // emergency_voting_period
+        8 +  // emergency_proposal_threshold
         8 +  // proposal_count
         1;   // bump
 }
 
+/// Denominator used for the approval threshold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Debug)]
+pub enum ApprovalBasis {
+    /// votes_for / (votes_for + votes_against)
+    #[default]
+    OfCast,
+    /// votes_for / total_supply
+    OfSupply,
+}
+
 /// A governance proposal
 #[account]
 pub struct Proposal {
@@ -50,14 +89,22 @@ pub struct Proposal {
     pub proposer: Pubkey,
     /// The action to execute if passed
     pub action: GovernanceAction,
-    /// Human-readable description
+    /// Human-readable description, or a short title when description_is_hash is set
     pub description: String,
+    /// Full description lives off-chain (IPFS/Arweave) and is anchored by description_hash
+    pub description_is_hash: bool,
+    /// Content hash of the off-chain description (zeroed when stored inline)
+    pub description_hash: [u8; 32],
     /// Weighted votes in favor
     pub votes_for: u64,
     /// Weighted votes against
     pub votes_against: u64,
     /// Weighted abstentions
     pub votes_abstain: u64,
+    /// Number of distinct voters
+    pub voter_count: u64,
+    /// Created as an emergency proposal (no voting delay, shortened period)
+    pub emergency: bool,
     /// Current status
     pub status: ProposalStatus,
     /// When voting opens
@@ -80,10 +127,14 @@ impl Proposal {
         32 + // token_config
         32 + // proposer
         GovernanceAction::LEN +
-        (4 + 500) + // description
+        (4 + Self::MAX_DESCRIPTION_LEN) + // description
+        1 +  // description_is_hash
+        32 + // description_hash
         8 +  // votes_for
         8 +  // votes_against
         8 +  // votes_abstain
+        8 +  // voter_count
+        1 +  // emergency
         1 +  // status
         8 +  // voting_starts
         8 +  // voting_ends
@@ -91,6 +142,20 @@ impl Proposal {
         (1 + 8) + // executed_at Option<i64>
         8 +  // snapshot_slot
         1;   // bump
+
+    /// Max inline description length
+    pub const MAX_DESCRIPTION_LEN: usize = 500;
+    /// Max title length when the description is stored off-chain
+    pub const MAX_TITLE_LEN: usize = 64;
+
+    /// Account size; hashed descriptions only reserve room for a short title
+    pub fn space(description_is_hash: bool) -> usize {
+        if description_is_hash {
+            Self::LEN - Self::MAX_DESCRIPTION_LEN + Self::MAX_TITLE_LEN
+        } else {
+            Self::LEN
+        }
+    }
 }
 
 /// Actions that can be proposed via governance
@@ -101,12 +166,43 @@ pub enum GovernanceAction {
     InitiateStockSplit { multiplier: u8 },
     /// Change the token symbol
     UpdateSymbol { new_symbol: String },
-    /// Initiate a dividend distribution
-    InitiateDividend { payment_token: Pubkey, total_amount: u64 },
+    /// Initiate a dividend distribution as the token's round `round_id`
+    InitiateDividend { payment_token: Pubkey, total_amount: u64, round_id: u64 },
+    /// Set token-wide restrictions for wallets without their own WalletRestrictions
+    SetDefaultRestrictions { daily_limit: Option<u64>, lockout_until: Option<i64> },
+    /// Pause or unpause the token
+    SetPaused { paused: bool },
 }
 
 impl GovernanceAction {
     pub const LEN: usize = 1 + 32 + 8 + 10; // Enum variant + largest payload
+
+    /// Check action parameters against the token program's rules
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            GovernanceAction::InitiateStockSplit { multiplier } => {
+                // Matches chainequity_token's InvalidSplitRatio rule
+                require!(*multiplier > 1, GovernanceError::InvalidAction);
+            }
+            GovernanceAction::UpdateSymbol { new_symbol } => {
+                // Matches the factory's SymbolTooLong rule
+                require!(
+                    !new_symbol.is_empty() && new_symbol.len() <= 10,
+                    GovernanceError::InvalidAction
+                );
+            }
+            GovernanceAction::InitiateDividend { total_amount, .. } => {
+                require!(*total_amount > 0, GovernanceError::InvalidAction);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Actions time-critical enough to skip the voting delay as an emergency proposal
+    pub fn is_emergency_eligible(&self) -> bool {
+        matches!(self, GovernanceAction::SetPaused { .. })
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Debug)]
@@ -147,6 +243,27 @@ impl VoteRecord {
         1;   // bump
 }
 
+/// Per-proposer record used to enforce the proposal cooldown
+#[account]
+pub struct ProposerRecord {
+    /// Governance config this record belongs to
+    pub governance_config: Pubkey,
+    /// Proposer wallet
+    pub proposer: Pubkey,
+    /// When the proposer last created a proposal
+    pub last_proposal_ts: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ProposerRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // governance_config
+        32 + // proposer
+        8 +  // last_proposal_ts
+        1;   // bump
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum Vote {
     For,
@@ -154,7 +271,34 @@ pub enum Vote {
     Abstain,
 }
 
+/// Marker created once per executed proposal so downstream actions
+/// (e.g., dividend round creation) can be performed at most once
+#[account]
+pub struct ExecutedActionMarker {
+    /// Proposal that was executed
+    pub proposal: Pubkey,
+    /// Who executed the proposal (only they can consume the marker)
+    pub executed_by: Pubkey,
+    /// Whether the downstream action has been performed
+    pub consumed: bool,
+    /// When executed
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ExecutedActionMarker {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // executed_by
+        1 +  // consumed
+        8 +  // created_at
+        1;   // bump
+}
+
 // PDA Seeds
 pub const GOVERNANCE_CONFIG_SEED: &[u8] = b"governance_config";
 pub const PROPOSAL_SEED: &[u8] = b"proposal";
 pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
+pub const ACTION_MARKER_SEED: &[u8] = b"action_marker";
+pub const PROPOSER_RECORD_SEED: &[u8] = b"proposer_record";
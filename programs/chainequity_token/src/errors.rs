@@ -18,19 +18,68 @@ pub enum TokenError {
     #[msg("Wallet is already on allowlist")]
     AlreadyOnAllowlist,
 
+    #[msg("Invalid KYC level")]
+    InvalidKycLevel,
+
+    #[msg("Allowlist entry is not pending review")]
+    EntryNotPending,
+
+    #[msg("A pending entry must be approved by someone other than its submitter")]
+    ApproverIsSubmitter,
+
     // Transfer errors
+    #[msg("Token account is frozen")]
+    AccountFrozen,
+
     #[msg("Token transfers are paused")]
     TransfersPaused,
 
+    #[msg("Token has been retired")]
+    TokenRetired,
+
     #[msg("Wallet is in lockout period")]
     InLockoutPeriod,
 
     #[msg("Transfer exceeds daily limit")]
     DailyLimitExceeded,
 
-    #[msg("Transfer would exceed maximum balance")]
+    #[msg("Transfer cooldown is still active")]
+    TransferCooldownActive,
+
+    #[msg("Transfer exceeds the limit for the sender's KYC level")]
+    KycTierLimitExceeded,
+
+    #[msg("Multisig transaction is not an executed restrictions update for this wallet")]
+    InvalidMultisigTransaction,
+
+    #[msg("Multisig transaction has already been applied or is older than the last applied one")]
+    MultisigTransactionAlreadyApplied,
+
+    /// Malformed transfer other than a zero amount (self-transfer, nothing delivered, ...)
+    #[msg("Invalid transfer: sender and recipient must differ and the recipient must receive a non-zero amount")]
+    InvalidTransfer,
+
+    #[msg("Transfer fee cannot exceed 10000 basis points")]
+    InvalidTransferFee,
+
+    #[msg("Fee treasury account missing or does not match the configured treasury")]
+    InvalidFeeTreasury,
+
+    #[msg("Display decimals exceed the maximum")]
+    InvalidDisplayDecimals,
+
+    #[msg("Upgrade timelock is below the minimum")]
+    InvalidUpgradeTimelock,
+
+    #[msg("Adding this wallet would exceed the token's maximum number of holders")]
+    MaxHoldersExceeded,
+
+    #[msg("Transfer or mint would exceed maximum balance")]
     MaxBalanceExceeded,
 
+    #[msg("Restriction values are zero or exceed the total supply")]
+    InvalidRestriction,
+
     #[msg("Insufficient balance")]
     InsufficientBalance,
 
@@ -53,12 +102,51 @@ pub enum TokenError {
     #[msg("Invalid vesting duration")]
     InvalidVestingDuration,
 
+    #[msg("Start time is too far in the past (set backdated to allow)")]
+    InvalidStartTime,
+
     #[msg("Termination notes too long (max 200 characters)")]
     TerminationNotesTooLong,
 
+    #[msg("Vesting has already begun; use terminate instead")]
+    VestingAlreadyStarted,
+
+    #[msg("Beneficiary has reached the maximum number of vesting schedules")]
+    VestingIndexFull,
+
+    #[msg("Additional acceleration exceeds the unvested remainder")]
+    InvalidAcceleration,
+
+    #[msg("Vesting schedule still has unreleased tokens owed to the beneficiary")]
+    VestingNotSettled,
+
+    #[msg("Escrow still holds tokens")]
+    EscrowNotEmpty,
+
+    #[msg("Vesting schedule has released every interval")]
+    VestingFullyReleased,
+
+    #[msg("New beneficiary must differ from the current beneficiary")]
+    InvalidBeneficiary,
+
+    #[msg("A vesting schedule already exists for this beneficiary and start time")]
+    VestingScheduleExists,
+
+    #[msg("Vesting amount must be at least one unit per interval")]
+    VestingAmountTooSmall,
+
+    #[msg("Too many vesting schedules in one batch release")]
+    ReleaseBatchTooLarge,
+
+    #[msg("Remaining accounts are missing or do not match the expected layout")]
+    InvalidRemainingAccounts,
+
     #[msg("This feature is not enabled for this token")]
     FeatureDisabled,
 
+    #[msg("Enabled features cannot be disabled")]
+    FeatureCannotBeDisabled,
+
     // Dividend errors
     #[msg("Dividend already claimed")]
     AlreadyClaimed,
@@ -66,6 +154,30 @@ pub enum TokenError {
     #[msg("Dividend round expired")]
     DividendExpired,
 
+    #[msg("Dividend round is not yet claimable")]
+    DividendNotYetClaimable,
+
+    #[msg("Dividend claim window must open before it expires")]
+    InvalidClaimWindow,
+
+    #[msg("New dividend expiry must be in the future and later than the current expiry")]
+    InvalidDividendExpiry,
+
+    #[msg("Snapshot slot is in the future or too old")]
+    InvalidSnapshotSlot,
+
+    #[msg("Payment token is not on the token's allowed list for dividends")]
+    PaymentTokenNotAllowed,
+
+    #[msg("Too many allowed payment tokens")]
+    TooManyPaymentTokens,
+
+    #[msg("Dividend pool is too small for the supply; every share would receive zero")]
+    DividendPerShareZero,
+
+    #[msg("Dividend entitlement is below the round's minimum claim")]
+    BelowMinimumClaim,
+
     #[msg("Dividend round is not active")]
     DividendNotActive,
 
@@ -78,6 +190,24 @@ pub enum TokenError {
     #[msg("Insufficient funds in dividend pool")]
     InsufficientPoolFunds,
 
+    #[msg("Round ID must be the next sequential dividend round")]
+    InvalidRoundId,
+
+    #[msg("Dividend round is still active and not expired")]
+    DividendRoundStillActive,
+
+    #[msg("Treasury account required to sweep residual dividend funds")]
+    TreasuryRequired,
+
+    #[msg("No dividend dust to sweep")]
+    NoDustToSweep,
+
+    #[msg("Snapshot cannot change after claims have been made")]
+    DividendSnapshotLocked,
+
+    #[msg("Wallet has opted out of dividends")]
+    DividendOptedOut,
+
     // Corporate action errors
     #[msg("Invalid split ratio")]
     InvalidSplitRatio,
@@ -85,6 +215,15 @@ pub enum TokenError {
     #[msg("Split already in progress")]
     SplitInProgress,
 
+    #[msg("Split batch too large")]
+    SplitBatchTooLarge,
+
+    #[msg("Split batch holders must be in ascending order after the last processed holder")]
+    SplitHoldersOutOfOrder,
+
+    #[msg("Split would push the cumulative split multiplier past its maximum")]
+    SplitMultiplierExceeded,
+
     #[msg("Symbol cannot be empty")]
     SymbolEmpty,
 
@@ -100,4 +239,19 @@ pub enum TokenError {
 
     #[msg("Invalid amount")]
     InvalidAmount,
+
+    #[msg("Mint authority has not been transferred to the token program")]
+    MintAuthorityNotInitialized,
+
+    #[msg("Too many recipients in one mint batch")]
+    MintBatchTooLarge,
+
+    #[msg("Governed tokens need an executed dividend proposal and its action marker")]
+    GovernanceApprovalRequired,
+
+    #[msg("Proposal does not authorize this dividend round")]
+    GovernanceActionMismatch,
+
+    #[msg("Cannot top up a round whose dust has already been swept")]
+    DividendDustAlreadySwept,
 }
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{AllowlistStatus, TerminationType, VestingInterval};
+use chainequity_factory::state::TokenFeatures;
+use crate::state::{AllowlistStatus, DividendKind, RemainderPolicy, TerminationType, VestingInterval};
 
 // ============================================================================
 // ALLOWLIST EVENTS
@@ -9,18 +10,50 @@ use crate::state::{AllowlistStatus, TerminationType, VestingInterval};
 pub struct WalletApproved {
     pub token_config: Pubkey,
     pub wallet: Pubkey,
+    pub kyc_level: u8,
     pub approved_by: Pubkey,
     pub slot: u64,
 }
 
+#[event]
+pub struct WalletSubmitted {
+    pub token_config: Pubkey,
+    pub wallet: Pubkey,
+    pub kyc_level: u8,
+    pub submitted_by: Pubkey,
+    pub slot: u64,
+}
+
 #[event]
 pub struct WalletRevoked {
     pub token_config: Pubkey,
     pub wallet: Pubkey,
+    /// Status of the entry before it was closed
+    pub prior_status: AllowlistStatus,
+    /// KYC level of the entry before it was closed
+    pub kyc_level: u8,
     pub revoked_by: Pubkey,
     pub slot: u64,
 }
 
+#[event]
+pub struct DividendOptOutChanged {
+    pub token_config: Pubkey,
+    pub wallet: Pubkey,
+    pub opt_out: bool,
+    pub changed_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FeeExemptChanged {
+    pub token_config: Pubkey,
+    pub wallet: Pubkey,
+    pub exempt: bool,
+    pub changed_by: Pubkey,
+    pub slot: u64,
+}
+
 #[event]
 pub struct AllowlistStatusChanged {
     pub token_config: Pubkey,
@@ -51,6 +84,10 @@ pub struct TokensTransferred {
     pub from: Pubkey,
     pub to: Pubkey,
     pub amount: u64,
+    /// Portion of amount routed to the fee treasury
+    pub fee: u64,
+    /// Fee skipped because the sender or recipient is fee-exempt
+    pub fee_exempt: bool,
     pub slot: u64,
 }
 
@@ -83,6 +120,8 @@ pub struct VestingScheduleCreated {
     pub total_intervals: u64,
     /// Amount released per interval
     pub amount_per_interval: u64,
+    /// How the per-interval remainder is distributed
+    pub remainder_policy: RemainderPolicy,
     pub created_by: Pubkey,
     pub slot: u64,
 }
@@ -116,6 +155,48 @@ pub struct VestingTerminated {
     pub slot: u64,
 }
 
+#[event]
+pub struct VestingCancelled {
+    pub token_config: Pubkey,
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub returned_to_treasury: u64,
+    pub cancelled_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VestingScheduleToppedUp {
+    pub token_config: Pubkey,
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub additional_amount: u64,
+    pub new_total_amount: u64,
+    pub amount_per_interval: u64,
+    pub added_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VestingScheduleClosed {
+    pub token_config: Pubkey,
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub funder: Pubkey,
+    pub closed_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VestingBeneficiaryMigrated {
+    pub token_config: Pubkey,
+    pub schedule: Pubkey,
+    pub old_beneficiary: Pubkey,
+    pub new_beneficiary: Pubkey,
+    pub migrated_by: Pubkey,
+    pub slot: u64,
+}
+
 // ============================================================================
 // RESTRICTION EVENTS
 // ============================================================================
@@ -127,6 +208,36 @@ pub struct WalletRestrictionsUpdated {
     pub daily_limit: Option<u64>,
     pub lockout_until: Option<i64>,
     pub max_balance: Option<u64>,
+    pub min_transfer_interval: Option<u64>,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+/// Emitted alongside WalletRestrictionsUpdated when an explicit update removes every limit
+#[event]
+pub struct WalletRestrictionsCleared {
+    pub token_config: Pubkey,
+    pub wallet: Pubkey,
+    pub previous_daily_limit: Option<u64>,
+    pub previous_lockout_until: Option<i64>,
+    pub previous_max_balance: Option<u64>,
+    pub previous_min_transfer_interval: Option<u64>,
+    pub cleared_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct LockoutExpired {
+    pub token_config: Pubkey,
+    pub wallet: Pubkey,
+    pub lockout_until: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct KycLevelLimitsUpdated {
+    pub token_config: Pubkey,
+    pub limits: [Option<u64>; 4],
     pub updated_by: Pubkey,
     pub slot: u64,
 }
@@ -141,23 +252,96 @@ pub struct DividendRoundCreated {
     pub round: Pubkey,
     pub round_id: u64,
     pub payment_token: Pubkey,
+    pub kind: DividendKind,
     pub total_pool: u64,
     pub amount_per_share: u64,
     pub snapshot_slot: u64,
+    pub claimable_from: Option<i64>,
     pub expires_at: Option<i64>,
+    pub min_claim: u64,
     pub created_by: Pubkey,
     pub slot: u64,
 }
 
+#[event]
+pub struct DividendSnapshotTaken {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub supply_at_snapshot: u64,
+    pub amount_per_share: u64,
+    pub snapshot_slot: u64,
+    pub taken_by: Pubkey,
+}
+
 #[event]
 pub struct DividendClaimed {
     pub token_config: Pubkey,
     pub round: Pubkey,
     pub wallet: Pubkey,
+    pub kind: DividendKind,
     pub amount: u64,
     pub slot: u64,
 }
 
+#[event]
+pub struct DividendReclaimed {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub reclaimed_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DividendRoundToppedUp {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub amount: u64,
+    pub new_total_pool: u64,
+    pub new_amount_per_share: u64,
+    pub topped_up_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DividendDustSwept {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub amount: u64,
+    pub swept_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DividendClaimClosed {
+    pub round: Pubkey,
+    pub wallet: Pubkey,
+    pub claimed: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DividendExpiryExtended {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub round_id: u64,
+    pub old_expiry: i64,
+    pub new_expiry: i64,
+    pub extended_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DividendRoundClosed {
+    pub token_config: Pubkey,
+    pub round: Pubkey,
+    pub round_id: u64,
+    pub residual_swept: u64,
+    pub closed_by: Pubkey,
+    pub slot: u64,
+}
+
 // ============================================================================
 // CORPORATE ACTION EVENTS
 // ============================================================================
@@ -176,8 +360,9 @@ pub struct StockSplitExecuted {
 #[event]
 pub struct SplitBatchProcessed {
     pub token_config: Pubkey,
-    pub batch_index: u32,
+    pub batch_number: u32,
     pub accounts_processed: u32,
+    pub last_holder: Pubkey,
     pub slot: u64,
 }
 
@@ -201,3 +386,81 @@ pub struct TokenPausedChanged {
     pub changed_by: Pubkey,
     pub slot: u64,
 }
+
+#[event]
+pub struct TransferFeeUpdated {
+    pub token_config: Pubkey,
+    pub transfer_fee_bps: Option<u16>,
+    pub fee_treasury: Pubkey,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SupplyReconciled {
+    pub token_config: Pubkey,
+    pub mint: Pubkey,
+    pub old_total_supply: u64,
+    pub new_total_supply: u64,
+    pub reconciled_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MaxHoldersUpdated {
+    pub token_config: Pubkey,
+    pub max_holders: Option<u32>,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DisplayDecimalsUpdated {
+    pub token_config: Pubkey,
+    pub old_display_decimals: u8,
+    pub new_display_decimals: u8,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AllowedPaymentTokensUpdated {
+    pub token_config: Pubkey,
+    pub allowed_payment_tokens: Vec<Pubkey>,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FeaturesUpdated {
+    pub token_config: Pubkey,
+    pub old_features: TokenFeatures,
+    pub new_features: TokenFeatures,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct UpgradeTimelockUpdated {
+    pub token_config: Pubkey,
+    pub old_timelock: i64,
+    pub new_timelock: i64,
+    pub updated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TokenDeactivated {
+    pub token_config: Pubkey,
+    pub deactivated_by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MintAuthorityAccepted {
+    pub token_config: Pubkey,
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub accepted_by: Pubkey,
+    pub slot: u64,
+}
@@ -1,18 +1,41 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token_2022::Token2022;
-use anchor_spl::token_interface::Mint;
-use chainequity_factory::instructions::create_token::TokenConfig;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
+use chainequity_factory::state::TokenFeatures;
 
 use crate::errors::TokenError;
-use crate::events::TokenPausedChanged;
-use crate::state::{MintAuthority, MINT_AUTHORITY_SEED};
+use crate::events::{TokenPausedChanged, TokenDeactivated, TransferFeeUpdated, SupplyReconciled, MaxHoldersUpdated, DisplayDecimalsUpdated, UpgradeTimelockUpdated, FeaturesUpdated, AllowedPaymentTokensUpdated, MintAuthorityAccepted};
+use crate::state::{AdminAction, AuditLog, MintAuthority, AUDIT_LOG_SEED, MINT_AUTHORITY_SEED};
 
 #[derive(Accounts)]
 pub struct SetTokenPaused<'info> {
     #[account(mut)]
     pub token_config: Account<'info, TokenConfig>,
 
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn set_paused_handler(ctx: Context<SetTokenPaused>, paused: bool) -> Result<()> {
@@ -20,6 +43,8 @@ pub fn set_paused_handler(ctx: Context<SetTokenPaused>, paused: bool) -> Result<
     token_config.is_paused = paused;
 
     let clock = Clock::get()?;
+    let action = if paused { AdminAction::Paused } else { AdminAction::Unpaused };
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, action, ctx.accounts.authority.key(), &clock);
 
     emit!(TokenPausedChanged {
         token_config: token_config.key(),
@@ -33,6 +58,471 @@ pub fn set_paused_handler(ctx: Context<SetTokenPaused>, paused: bool) -> Result<
     Ok(())
 }
 
+// ============================================================================
+// TRANSFER FEE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetTransferFee<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Token account that will receive transfer fees
+    #[account(
+        token::mint = token_config.mint,
+    )]
+    pub fee_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_transfer_fee_handler(ctx: Context<SetTransferFee>, transfer_fee_bps: Option<u16>) -> Result<()> {
+    if let Some(bps) = transfer_fee_bps {
+        require!(bps <= 10_000, TokenError::InvalidTransferFee);
+    }
+
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.transfer_fee_bps = transfer_fee_bps;
+    token_config.fee_treasury = ctx.accounts.fee_treasury.key();
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::TransferFeeChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(TransferFeeUpdated {
+        token_config: token_config.key(),
+        transfer_fee_bps,
+        fee_treasury: token_config.fee_treasury,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Transfer fee set to {:?} bps", transfer_fee_bps);
+
+    Ok(())
+}
+
+// ============================================================================
+// DIVIDEND PAYMENT TOKENS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetAllowedPaymentTokens<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the list of mints accepted for dividend rounds (empty = any mint)
+pub fn set_allowed_payment_tokens_handler(
+    ctx: Context<SetAllowedPaymentTokens>,
+    payment_tokens: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        payment_tokens.len() <= TokenConfig::MAX_PAYMENT_TOKENS,
+        TokenError::TooManyPaymentTokens
+    );
+
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.allowed_payment_tokens = payment_tokens.clone();
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::PaymentTokensChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(AllowedPaymentTokensUpdated {
+        token_config: token_config.key(),
+        allowed_payment_tokens: payment_tokens,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Allowed dividend payment tokens set to {} mints", token_config.allowed_payment_tokens.len());
+
+    Ok(())
+}
+
+// ============================================================================
+// RECONCILE SUPPLY
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ReconcileSupply<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set token_config.total_supply to the mint's on-chain supply, correcting drift from
+/// mints or burns made outside this program
+pub fn reconcile_supply_handler(ctx: Context<ReconcileSupply>) -> Result<()> {
+    let mint_supply = ctx.accounts.mint.supply;
+    let token_config = &mut ctx.accounts.token_config;
+    let old_total_supply = token_config.total_supply;
+    token_config.total_supply = mint_supply;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::SupplyReconciled, ctx.accounts.authority.key(), &clock);
+
+    emit!(SupplyReconciled {
+        token_config: token_config.key(),
+        mint: ctx.accounts.mint.key(),
+        old_total_supply,
+        new_total_supply: mint_supply,
+        reconciled_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Reconciled total supply: {} -> {}", old_total_supply, mint_supply);
+
+    Ok(())
+}
+
+// ============================================================================
+// MAX HOLDERS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetMaxHolders<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Lowering the cap below the current allowlist_count only blocks new approvals
+pub fn set_max_holders_handler(ctx: Context<SetMaxHolders>, max_holders: Option<u32>) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.max_holders = max_holders;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::MaxHoldersChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(MaxHoldersUpdated {
+        token_config: token_config.key(),
+        max_holders,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Max holders set to {:?}", max_holders);
+
+    Ok(())
+}
+
+// ============================================================================
+// DISPLAY DECIMALS
+// ============================================================================
+
+/// Mint decimals are immutable; display_decimals only changes how clients render amounts
+#[derive(Accounts)]
+pub struct SetTokenDecimalsDisplay<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_decimals_display_handler(ctx: Context<SetTokenDecimalsDisplay>, display_decimals: u8) -> Result<()> {
+    require!(display_decimals <= TokenConfig::MAX_DECIMALS, TokenError::InvalidDisplayDecimals);
+
+    let token_config = &mut ctx.accounts.token_config;
+    let old_display_decimals = token_config.display_decimals;
+    token_config.display_decimals = display_decimals;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::DisplayDecimalsChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(DisplayDecimalsUpdated {
+        token_config: token_config.key(),
+        old_display_decimals,
+        new_display_decimals: display_decimals,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Display decimals changed from {} to {}", old_display_decimals, display_decimals);
+
+    Ok(())
+}
+
+// ============================================================================
+// UPGRADE TIMELOCK
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetUpgradeTimelock<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_upgrade_timelock_handler(ctx: Context<SetUpgradeTimelock>, seconds: i64) -> Result<()> {
+    require!(seconds >= TokenConfig::MIN_UPGRADE_TIMELOCK, TokenError::InvalidUpgradeTimelock);
+
+    let token_config = &mut ctx.accounts.token_config;
+    let old_timelock = token_config.upgrade_timelock;
+    token_config.upgrade_timelock = seconds;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::UpgradeTimelockChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(UpgradeTimelockUpdated {
+        token_config: token_config.key(),
+        old_timelock,
+        new_timelock: seconds,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Upgrade timelock changed from {}s to {}s", old_timelock, seconds);
+
+    Ok(())
+}
+
+// ============================================================================
+// FEATURES
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateFeatures<'info> {
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Enable additional feature flags. Flags can only be turned on: disabling one would
+/// strand existing schedules, rounds or restrictions that depend on it.
+pub fn update_features_handler(ctx: Context<UpdateFeatures>, features: TokenFeatures) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    let old_features = token_config.features.clone();
+
+    let disables = (old_features.vesting_enabled && !features.vesting_enabled)
+        || (old_features.governance_enabled && !features.governance_enabled)
+        || (old_features.dividends_enabled && !features.dividends_enabled)
+        || (old_features.transfer_restrictions_enabled && !features.transfer_restrictions_enabled)
+        || (old_features.upgradeable && !features.upgradeable);
+    require!(!disables, TokenError::FeatureCannotBeDisabled);
+
+    token_config.features = features.clone();
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::FeaturesUpdated, ctx.accounts.authority.key(), &clock);
+
+    emit!(FeaturesUpdated {
+        token_config: token_config.key(),
+        old_features,
+        new_features: features,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Updated features for token {}", token_config.symbol);
+
+    Ok(())
+}
+
+// ============================================================================
+// DEACTIVATE TOKEN
+// ============================================================================
+
+/// Permanently retire a token (delisting/dissolution). Unlike a pause this is terminal.
+#[derive(Accounts)]
+pub struct DeactivateToken<'info> {
+    #[account(
+        mut,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deactivate_handler(ctx: Context<DeactivateToken>) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.is_active = false;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::Deactivated, ctx.accounts.authority.key(), &clock);
+
+    emit!(TokenDeactivated {
+        token_config: token_config.key(),
+        deactivated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Token retired: {}", token_config.symbol);
+
+    Ok(())
+}
+
 // ============================================================================
 // INITIALIZE MINT AUTHORITY
 // ============================================================================
@@ -80,6 +570,7 @@ pub fn initialize_mint_authority_handler(ctx: Context<InitializeMintAuthority>)
     let mint_authority = &mut ctx.accounts.mint_authority;
     mint_authority.token_config = token_config.key();
     mint_authority.mint = ctx.accounts.mint.key();
+    mint_authority.authority_transferred = false;
     mint_authority.bump = ctx.bumps.mint_authority;
 
     msg!(
@@ -88,8 +579,95 @@ pub fn initialize_mint_authority_handler(ctx: Context<InitializeMintAuthority>)
         token_config.symbol
     );
     msg!(
-        "IMPORTANT: Call factory.transfer_mint_authority to complete the setup"
+        "IMPORTANT: Call factory.transfer_mint_authority, then accept_mint_authority to complete the setup"
     );
 
     Ok(())
 }
+
+// ============================================================================
+// ACCEPT MINT AUTHORITY
+// ============================================================================
+
+/// Accept the mint authority once the factory has handed it to the MintAuthority PDA.
+/// The factory cannot write to accounts owned by this program, so the flag is set
+/// here after verifying the mint's authority on-chain.
+#[derive(Accounts)]
+pub struct AcceptMintAuthority<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_AUTHORITY_SEED, token_config.key().as_ref()],
+        bump = mint_authority.bump,
+        constraint = mint_authority.mint == mint.key() @ TokenError::Unauthorized,
+        constraint = mint.mint_authority == COption::Some(mint_authority.key()) @ TokenError::MintAuthorityNotInitialized,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn accept_mint_authority_handler(ctx: Context<AcceptMintAuthority>) -> Result<()> {
+    let mint_authority = &mut ctx.accounts.mint_authority;
+    mint_authority.authority_transferred = true;
+
+    let clock = Clock::get()?;
+
+    emit!(MintAuthorityAccepted {
+        token_config: ctx.accounts.token_config.key(),
+        mint: ctx.accounts.mint.key(),
+        mint_authority: mint_authority.key(),
+        accepted_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Mint authority accepted for token: {}", ctx.accounts.token_config.symbol);
+
+    Ok(())
+}
+
+// ============================================================================
+// AUDIT LOG
+// ============================================================================
+
+/// Append an admin action to the token's audit log, initializing the log on first use
+pub(crate) fn record_admin_action(
+    audit_log: &mut AuditLog,
+    token_config: Pubkey,
+    bump: u8,
+    action: AdminAction,
+    actor: Pubkey,
+    clock: &Clock,
+) {
+    if audit_log.token_config == Pubkey::default() {
+        audit_log.token_config = token_config;
+        audit_log.bump = bump;
+    }
+    audit_log.record(action, actor, clock.unix_timestamp);
+}
+
+#[derive(Accounts)]
+pub struct GetAuditLog<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+}
+
+/// Read-only: returns the recorded admin actions, oldest first, as a Borsh-encoded
+/// Vec<AuditEntry> via return data
+pub fn get_audit_log_handler(ctx: Context<GetAuditLog>) -> Result<()> {
+    let entries = ctx.accounts.audit_log.chronological();
+    set_return_data(&entries.try_to_vec()?);
+
+    Ok(())
+}
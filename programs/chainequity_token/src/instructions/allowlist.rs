@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
 
-use crate::state::{AllowlistEntry, AllowlistStatus, ALLOWLIST_SEED};
+use crate::state::{AdminAction, AllowlistEntry, AllowlistStatus, AuditLog, ALLOWLIST_SEED, AUDIT_LOG_SEED};
 use crate::errors::TokenError;
-use crate::events::{WalletApproved, WalletRevoked, AllowlistStatusChanged};
+use crate::events::{WalletApproved, WalletSubmitted, WalletRevoked, AllowlistStatusChanged, DividendOptOutChanged, FeeExemptChanged};
+use crate::instructions::admin::record_admin_action;
 
 #[derive(Accounts)]
 pub struct AddToAllowlist<'info> {
     #[account(
+        mut,
         constraint = !token_config.is_paused @ TokenError::TransfersPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
@@ -24,37 +26,155 @@ pub struct AddToAllowlist<'info> {
     /// CHECK: Wallet being added to allowlist
     pub wallet: UncheckedAccount<'info>,
 
+    /// Token admin multisig - submitter must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn add_handler(ctx: Context<AddToAllowlist>) -> Result<()> {
-    let entry = &mut ctx.accounts.allowlist_entry;
+pub fn add_handler(mut ctx: Context<AddToAllowlist>, kyc_level: u8) -> Result<()> {
     let clock = Clock::get()?;
+    init_entry(&mut ctx, kyc_level, AllowlistStatus::Active, &clock)?;
+
+    emit!(WalletApproved {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: ctx.accounts.wallet.key(),
+        kyc_level,
+        approved_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Added wallet to allowlist: {}", ctx.accounts.wallet.key());
+
+    Ok(())
+}
+
+/// Submit a wallet for review. The entry is created as Pending and does not
+/// grant transfer rights until a different multisig signer approves it.
+pub fn add_pending_handler(mut ctx: Context<AddToAllowlist>, kyc_level: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    init_entry(&mut ctx, kyc_level, AllowlistStatus::Pending, &clock)?;
+
+    emit!(WalletSubmitted {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: ctx.accounts.wallet.key(),
+        kyc_level,
+        submitted_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Submitted wallet for allowlist review: {}", ctx.accounts.wallet.key());
+
+    Ok(())
+}
+
+fn init_entry(
+    ctx: &mut Context<AddToAllowlist>,
+    kyc_level: u8,
+    status: AllowlistStatus,
+    clock: &Clock,
+) -> Result<()> {
+    require!((kyc_level as usize) < TokenConfig::KYC_LEVELS, TokenError::InvalidKycLevel);
+
+    let entry = &mut ctx.accounts.allowlist_entry;
 
     entry.token_config = ctx.accounts.token_config.key();
     entry.wallet = ctx.accounts.wallet.key();
     entry.approved_at = clock.unix_timestamp;
     entry.approved_by = ctx.accounts.authority.key();
-    entry.status = AllowlistStatus::Active;
+    entry.status = status;
+    entry.kyc_level = kyc_level;
+    entry.dividend_opt_out = false;
+    entry.fee_exempt = false;
     entry.bump = ctx.bumps.allowlist_entry;
 
+    count_new_entry(&mut ctx.accounts.token_config)
+}
+
+/// Count a newly created entry against the token's holder cap
+pub(crate) fn count_new_entry(token_config: &mut TokenConfig) -> Result<()> {
+    if let Some(max_holders) = token_config.max_holders {
+        require!(
+            token_config.allowlist_count < max_holders as u64,
+            TokenError::MaxHoldersExceeded
+        );
+    }
+    token_config.allowlist_count = token_config.allowlist_count
+        .checked_add(1)
+        .ok_or(TokenError::MathOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveAllowlistEntry<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), allowlist_entry.wallet.as_ref()],
+        bump = allowlist_entry.bump,
+        constraint = allowlist_entry.status == AllowlistStatus::Pending @ TokenError::EntryNotPending,
+        constraint = allowlist_entry.approved_by != authority.key() @ TokenError::ApproverIsSubmitter,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// Token admin multisig - approver must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Promote a Pending entry to Active. The approver must be a token multisig signer
+/// and differ from the submitter, and may override the submitted KYC level.
+pub fn approve_handler(ctx: Context<ApproveAllowlistEntry>, kyc_level: Option<u8>) -> Result<()> {
+    let clock = Clock::get()?;
+    let entry = &mut ctx.accounts.allowlist_entry;
+
+    if let Some(kyc_level) = kyc_level {
+        require!((kyc_level as usize) < TokenConfig::KYC_LEVELS, TokenError::InvalidKycLevel);
+        entry.kyc_level = kyc_level;
+    }
+    entry.status = AllowlistStatus::Active;
+    entry.approved_at = clock.unix_timestamp;
+    entry.approved_by = ctx.accounts.authority.key();
+
+    emit!(AllowlistStatusChanged {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: entry.wallet,
+        old_status: AllowlistStatus::Pending,
+        new_status: AllowlistStatus::Active,
+        changed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
     emit!(WalletApproved {
         token_config: ctx.accounts.token_config.key(),
-        wallet: ctx.accounts.wallet.key(),
+        wallet: entry.wallet,
+        kyc_level: entry.kyc_level,
         approved_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
 
-    msg!("Added wallet to allowlist: {}", ctx.accounts.wallet.key());
+    msg!("Approved pending allowlist entry: {}", entry.wallet);
 
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct RemoveFromAllowlist<'info> {
+    #[account(mut)]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -74,10 +194,16 @@ pub struct RemoveFromAllowlist<'info> {
 
 pub fn remove_handler(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
     let clock = Clock::get()?;
+    let entry = &ctx.accounts.allowlist_entry;
+
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.allowlist_count = token_config.allowlist_count.saturating_sub(1);
 
     emit!(WalletRevoked {
         token_config: ctx.accounts.token_config.key(),
         wallet: ctx.accounts.wallet.key(),
+        prior_status: entry.status.clone(),
+        kyc_level: entry.kyc_level,
         revoked_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
@@ -122,3 +248,209 @@ pub fn update_status_handler(
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct SetDividendOptOut<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), allowlist_entry.wallet.as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// Token admin multisig - required unless the wallet is opting itself out
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+    )]
+    pub multisig: Option<Account<'info, MultisigConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Exclude (or re-include) a wallet from dividend claims. Opted-out shares stay in the
+/// pool and are swept to the treasury when the round is closed.
+/// Callable by the wallet itself or by a token multisig signer.
+pub fn set_dividend_opt_out_handler(ctx: Context<SetDividendOptOut>, opt_out: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let is_self = authority == ctx.accounts.allowlist_entry.wallet;
+    let is_admin = ctx.accounts.multisig
+        .as_ref()
+        .is_some_and(|m| m.signers.contains(&authority));
+    require!(is_self || is_admin, TokenError::Unauthorized);
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.dividend_opt_out = opt_out;
+
+    let clock = Clock::get()?;
+
+    emit!(DividendOptOutChanged {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: entry.wallet,
+        opt_out,
+        changed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeExempt<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), allowlist_entry.wallet.as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Exempt (or un-exempt) a wallet from the transfer fee, as sender or recipient
+pub fn set_fee_exempt_handler(ctx: Context<SetFeeExempt>, exempt: bool) -> Result<()> {
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.fee_exempt = exempt;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, ctx.accounts.token_config.key(), ctx.bumps.audit_log, AdminAction::FeeExemptChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(FeeExemptChanged {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: entry.wallet,
+        exempt,
+        changed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateAllowlist<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), old_wallet.key().as_ref()],
+        bump = old_allowlist_entry.bump,
+        close = authority
+    )]
+    pub old_allowlist_entry: Account<'info, AllowlistEntry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AllowlistEntry::LEN,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), new_wallet.key().as_ref()],
+        bump
+    )]
+    pub new_allowlist_entry: Account<'info, AllowlistEntry>,
+
+    /// CHECK: Wallet whose approval is being moved
+    pub old_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Replacement wallet receiving the approval
+    pub new_wallet: UncheckedAccount<'info>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move an investor's allowlist approval (KYC level, approval record, status) to a new wallet
+pub fn migrate_handler(ctx: Context<MigrateAllowlist>) -> Result<()> {
+    let old_entry = &ctx.accounts.old_allowlist_entry;
+    let new_entry = &mut ctx.accounts.new_allowlist_entry;
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, ctx.accounts.token_config.key(), ctx.bumps.audit_log, AdminAction::AllowlistMigrated, ctx.accounts.authority.key(), &clock);
+
+    new_entry.token_config = ctx.accounts.token_config.key();
+    new_entry.wallet = ctx.accounts.new_wallet.key();
+    new_entry.approved_at = old_entry.approved_at;
+    new_entry.approved_by = old_entry.approved_by;
+    new_entry.status = old_entry.status.clone();
+    new_entry.kyc_level = old_entry.kyc_level;
+    new_entry.dividend_opt_out = old_entry.dividend_opt_out;
+    new_entry.fee_exempt = old_entry.fee_exempt;
+    new_entry.bump = ctx.bumps.new_allowlist_entry;
+
+    emit!(WalletRevoked {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: ctx.accounts.old_wallet.key(),
+        prior_status: old_entry.status.clone(),
+        kyc_level: old_entry.kyc_level,
+        revoked_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    emit!(WalletApproved {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: ctx.accounts.new_wallet.key(),
+        kyc_level: new_entry.kyc_level,
+        approved_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Migrated allowlist entry from {} to {}",
+        ctx.accounts.old_wallet.key(),
+        ctx.accounts.new_wallet.key()
+    );
+
+    Ok(())
+}
+
+/// Assert that `entry` is the allowlist entry for `wallet` on `token_config` and is Active.
+/// Shared by every instruction that gates on allowlist membership so the checks can't drift.
+pub fn require_active_allowlist(
+    entry: &AllowlistEntry,
+    token_config: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(entry.token_config, *token_config, TokenError::NotOnAllowlist);
+    require_keys_eq!(entry.wallet, *wallet, TokenError::NotOnAllowlist);
+    require!(entry.status == AllowlistStatus::Active, TokenError::WalletNotActive);
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
+use chainequity_factory::program::ChainequityFactory;
 
+use crate::state::{AdminAction, AuditLog, SplitProgress, AUDIT_LOG_SEED, SPLIT_PROGRESS_SEED};
 use crate::errors::TokenError;
 use crate::events::{StockSplitExecuted, SplitBatchProcessed, SymbolChanged};
+use crate::instructions::admin::record_admin_action;
 
 #[derive(Accounts)]
 pub struct ExecuteSplitBatch<'info> {
@@ -17,44 +20,132 @@ pub struct ExecuteSplitBatch<'info> {
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SplitProgress::LEN,
+        seeds = [SPLIT_PROGRESS_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub split_progress: Box<Account<'info, SplitProgress>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-    // Remaining accounts: token accounts to process in this batch
+    // Remaining accounts: holder token accounts for this batch, in ascending key order
 }
 
-pub fn split_batch_handler(
-    ctx: Context<ExecuteSplitBatch>,
+pub fn split_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSplitBatch<'info>>,
     split_ratio: u8,
-    batch_index: u32,
 ) -> Result<()> {
     require!(split_ratio > 1, TokenError::InvalidSplitRatio);
+    require!(!ctx.remaining_accounts.is_empty(), TokenError::InvalidRemainingAccounts);
+    require!(
+        ctx.remaining_accounts.len() <= SplitProgress::MAX_SPLIT_BATCH,
+        TokenError::SplitBatchTooLarge
+    );
 
     let clock = Clock::get()?;
     let accounts_processed = ctx.remaining_accounts.len() as u32;
-
-    // In production, would iterate through remaining_accounts and mint additional tokens
-    // to each holder based on split_ratio
+    let mint_key = ctx.accounts.mint.key();
+
+    let progress = &mut ctx.accounts.split_progress;
+    if progress.token_config == Pubkey::default() {
+        check_split_headroom(&ctx.accounts.token_config, split_ratio)?;
+        progress.token_config = ctx.accounts.token_config.key();
+        progress.split_ratio = split_ratio;
+        progress.bump = ctx.bumps.split_progress;
+    }
+    require!(progress.split_ratio == split_ratio, TokenError::SplitInProgress);
+
+    // Batches are idempotent: a batch that already succeeded is skipped on retry
+    let first_holder = ctx.remaining_accounts[0].key();
+    let last_holder = ctx.remaining_accounts[ctx.remaining_accounts.len() - 1].key();
+    if progress.is_holder_processed(&last_holder) {
+        msg!("Split batch ending at {} already processed, skipping", last_holder);
+        return Ok(());
+    }
+
+    // Each holder is processed exactly once: keys must be strictly ascending and
+    // continue past the last holder of the previous batch
+    require!(
+        !progress.is_holder_processed(&first_holder),
+        TokenError::SplitHoldersOutOfOrder
+    );
+    for pair in ctx.remaining_accounts.windows(2) {
+        require!(pair[0].key() < pair[1].key(), TokenError::SplitHoldersOutOfOrder);
+    }
+
+    // Every holder's post-split balance must fit in a u64. Name the offending account
+    // so one oversized holder cannot leave the split failing without explanation.
+    for holder_info in ctx.remaining_accounts.iter() {
+        let holder_account: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(holder_info)?;
+        require_keys_eq!(holder_account.mint, mint_key, TokenError::InvalidRemainingAccounts);
+        if holder_account.amount.checked_mul(split_ratio as u64).is_none() {
+            msg!("Split {}:1 would overflow the balance of token account {}", split_ratio, holder_info.key());
+            return Err(TokenError::MathOverflow.into());
+        }
+    }
+
+    // In production, would mint additional tokens to each holder based on split_ratio
+
+    progress.last_processed_holder = last_holder;
+    progress.batches_processed = progress.batches_processed
+        .checked_add(1)
+        .ok_or(TokenError::MathOverflow)?;
+    progress.accounts_processed = progress.accounts_processed
+        .checked_add(accounts_processed)
+        .ok_or(TokenError::MathOverflow)?;
 
     emit!(SplitBatchProcessed {
         token_config: ctx.accounts.token_config.key(),
-        batch_index,
+        batch_number: progress.batches_processed,
         accounts_processed,
+        last_holder,
         slot: clock.slot,
     });
 
-    msg!("Processed split batch {} with {} accounts", batch_index, accounts_processed);
+    msg!("Processed split batch {} with {} accounts", progress.batches_processed, accounts_processed);
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(split_ratio: u8)]
 pub struct FinalizeSplit<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
     pub token_config: Account<'info, TokenConfig>,
 
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Progress of the split batches (closed once the split is finalized)
+    #[account(
+        mut,
+        seeds = [SPLIT_PROGRESS_SEED, token_config.key().as_ref()],
+        bump = split_progress.bump,
+        constraint = split_progress.split_ratio == split_ratio @ TokenError::SplitInProgress,
+        close = authority,
+    )]
+    pub split_progress: Box<Account<'info, SplitProgress>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 }
@@ -64,6 +155,7 @@ pub fn finalize_split_handler(
     split_ratio: u8,
 ) -> Result<()> {
     require!(split_ratio > 1, TokenError::InvalidSplitRatio);
+    check_split_headroom(&ctx.accounts.token_config, split_ratio)?;
 
     let token_config = &mut ctx.accounts.token_config;
     let old_supply = token_config.total_supply;
@@ -79,13 +171,14 @@ pub fn finalize_split_handler(
         .ok_or(TokenError::MathOverflow)?;
 
     let clock = Clock::get()?;
+    let accounts_updated = ctx.accounts.split_progress.accounts_processed;
 
     emit!(StockSplitExecuted {
         token_config: token_config.key(),
         split_ratio,
         old_total_supply: old_supply,
         new_total_supply: token_config.total_supply,
-        accounts_updated: 0, // Would track actual count
+        accounts_updated,
         executed_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
@@ -97,9 +190,28 @@ pub fn finalize_split_handler(
     Ok(())
 }
 
+/// A split must keep the total supply within a u64 and the cumulative multiplier
+/// within the headroom reserved at token creation
+fn check_split_headroom(token_config: &TokenConfig, split_ratio: u8) -> Result<()> {
+    token_config.total_supply
+        .checked_mul(split_ratio as u64)
+        .ok_or(TokenError::MathOverflow)?;
+    let new_multiplier = token_config.split_multiplier
+        .checked_mul(split_ratio as u64)
+        .ok_or(TokenError::MathOverflow)?;
+    require!(
+        new_multiplier <= TokenConfig::MAX_SPLIT_MULTIPLIER,
+        TokenError::SplitMultiplierExceeded
+    );
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct ChangeSymbol<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -108,10 +220,37 @@ pub struct ChangeSymbol<'info> {
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// CHECK: Registry entry for the current symbol, validated and closed by the factory
+    #[account(mut)]
+    pub old_symbol_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Registry entry for the new symbol, validated and claimed by the factory
+    #[account(mut)]
+    pub new_symbol_registry: UncheckedAccount<'info>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub factory_program: Program<'info, ChainequityFactory>,
     pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn change_symbol_handler(
@@ -121,11 +260,27 @@ pub fn change_symbol_handler(
     require!(!new_symbol.is_empty(), TokenError::SymbolEmpty);
     require!(new_symbol.len() <= 10, TokenError::SymbolTooLong);
 
-    let token_config = &mut ctx.accounts.token_config;
-    let old_symbol = token_config.symbol.clone();
-    token_config.symbol = new_symbol.clone();
-
+    let old_symbol = ctx.accounts.token_config.symbol.clone();
+
+    // The factory owns the symbol registry and enforces uniqueness
+    chainequity_factory::cpi::reassign_symbol(
+        CpiContext::new(
+            ctx.accounts.factory_program.to_account_info(),
+            chainequity_factory::cpi::accounts::ReassignSymbol {
+                token_config: ctx.accounts.token_config.to_account_info(),
+                multisig: ctx.accounts.multisig.to_account_info(),
+                old_symbol_registry: ctx.accounts.old_symbol_registry.to_account_info(),
+                new_symbol_registry: ctx.accounts.new_symbol_registry.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+        ),
+        new_symbol.clone(),
+    )?;
+
+    let token_config = &ctx.accounts.token_config;
     let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::SymbolChanged, ctx.accounts.authority.key(), &clock);
 
     // In production, would also update Token-2022 metadata extension
 
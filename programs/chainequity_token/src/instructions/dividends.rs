@@ -1,17 +1,29 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022, TransferChecked};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, set_return_data};
+use anchor_spl::token_2022::{self, CloseAccount, Token2022, TransferChecked};
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
 
-use crate::state::{DividendRound, DividendClaim, DividendStatus, DIVIDEND_ROUND_SEED, DIVIDEND_CLAIM_SEED, DIVIDEND_POOL_SEED};
+use crate::state::{AllowlistEntry, DividendRound, DividendClaim, DividendKind, DividendStatus, ALLOWLIST_SEED, DIVIDEND_ROUND_SEED, DIVIDEND_CLAIM_SEED, DIVIDEND_POOL_SEED, GOVERNANCE_PROGRAM_ID, governance_proposal};
 use crate::errors::TokenError;
-use crate::events::{DividendRoundCreated, DividendClaimed};
+use crate::events::{DividendRoundCreated, DividendSnapshotTaken, DividendExpiryExtended, DividendClaimed, DividendClaimClosed, DividendRoundClosed, DividendDustSwept, DividendReclaimed, DividendRoundToppedUp};
+use crate::instructions::allowlist::require_active_allowlist;
+
+/// Fixed-point scale of `amount_per_share` (6 decimals). Higher precision would shrink
+/// the unclaimable dust but narrows the pool/supply range that fits in a u64; lower
+/// precision loses small pools entirely to truncation.
+pub const SHARE_PRECISION: u128 = 1_000_000;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
 pub struct CreateDividendRound<'info> {
     #[account(
+        mut,
         constraint = token_config.features.dividends_enabled @ TokenError::FeatureDisabled,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+        constraint = round_id == token_config.dividend_round_count @ TokenError::InvalidRoundId,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -48,11 +60,28 @@ pub struct CreateDividendRound<'info> {
             &round_id.to_le_bytes()
         ],
         bump,
-        token::mint = payment_token,
+        constraint = dividend_pool.mint == payment_token.key() @ TokenError::Unauthorized,
         token::authority = dividend_round,
     )]
     pub dividend_pool: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: Executed InitiateDividend proposal (required when governance is enabled);
+    /// owner, token and action are checked in the handler
+    #[account(
+        owner = GOVERNANCE_PROGRAM_ID @ TokenError::GovernanceActionMismatch,
+    )]
+    pub proposal: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The proposal's action marker, validated and consumed by the governance program
+    #[account(mut)]
+    pub action_marker: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Governance program, invoked to consume the action marker
+    #[account(
+        address = GOVERNANCE_PROGRAM_ID @ TokenError::GovernanceActionMismatch,
+    )]
+    pub governance_program: Option<UncheckedAccount<'info>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -60,16 +89,51 @@ pub struct CreateDividendRound<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_round_handler(
     ctx: Context<CreateDividendRound>,
     round_id: u64,
+    kind: DividendKind,
     total_pool: u64,
+    snapshot_slot: Option<u64>,
+    claimable_in_seconds: Option<u64>,
     expires_in_seconds: Option<u64>,
+    min_claim: u64,
 ) -> Result<()> {
     require!(total_pool > 0, TokenError::InvalidAmount);
+    if let (Some(claimable_in), Some(expires_in)) = (claimable_in_seconds, expires_in_seconds) {
+        require!(claimable_in < expires_in, TokenError::InvalidClaimWindow);
+    }
+
+    // A governed token's rounds come from executed proposals, each usable once
+    if ctx.accounts.token_config.features.governance_enabled {
+        consume_governance_approval(&ctx.accounts, total_pool, round_id)?;
+    }
 
     let clock = Clock::get()?;
     let token_config = &ctx.accounts.token_config;
+
+    // An explicit record point must already have passed and be recent enough to resolve
+    let snapshot_slot = match snapshot_slot {
+        Some(slot) => {
+            require!(slot <= clock.slot, TokenError::InvalidSnapshotSlot);
+            require!(
+                clock.slot - slot <= DividendRound::MAX_SNAPSHOT_AGE_SLOTS,
+                TokenError::InvalidSnapshotSlot
+            );
+            slot
+        }
+        None => clock.slot,
+    };
+
+    let allowed = &token_config.allowed_payment_tokens;
+    require!(
+        allowed.is_empty() || allowed.contains(&ctx.accounts.payment_token.key()),
+        TokenError::PaymentTokenNotAllowed
+    );
+
+    // A round against an empty cap table could never be claimed
+    require!(token_config.total_supply > 0, TokenError::NoEntitlement);
     let round = &mut ctx.accounts.dividend_round;
     let payment_token = &ctx.accounts.payment_token;
 
@@ -79,24 +143,35 @@ pub fn create_round_handler(
         TokenError::InsufficientFunds
     );
 
-    // Calculate amount per share (with 6 decimal precision)
-    let amount_per_share = if token_config.total_supply > 0 {
-        (total_pool as u128 * 1_000_000 / token_config.total_supply as u128) as u64
-    } else {
-        0
-    };
+    let amount_per_share = calculate_amount_per_share(total_pool, token_config.total_supply)?;
 
     round.token_config = token_config.key();
     round.id = round_id;
     round.payment_token = payment_token.key();
+    round.kind = kind.clone();
     round.total_pool = total_pool;
-    round.snapshot_slot = clock.slot;
+    round.snapshot_slot = snapshot_slot;
+    round.supply_at_snapshot = token_config.total_supply;
     round.amount_per_share = amount_per_share;
     round.status = DividendStatus::Active;
     round.created_at = clock.unix_timestamp;
+    round.claimable_from = claimable_in_seconds.map(|s| clock.unix_timestamp + s as i64);
     round.expires_at = expires_in_seconds.map(|s| clock.unix_timestamp + s as i64);
+    round.total_claimed = 0;
+    round.claims_count = 0;
+    round.min_claim = min_claim;
+    // Truncation in amount_per_share leaves a remainder no holder can claim
+    round.dust = total_pool.saturating_sub(calculate_entitlement(token_config.total_supply, amount_per_share));
+    round.dust_swept = false;
+    round.created_by = ctx.accounts.authority.key();
     round.bump = ctx.bumps.dividend_round;
 
+    // Advance the sequential round counter so IDs stay gap-free
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.dividend_round_count = token_config.dividend_round_count
+        .checked_add(1)
+        .ok_or(TokenError::MathOverflow)?;
+
     // Transfer funds from authority to dividend pool
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.source_token_account.to_account_info(),
@@ -113,10 +188,13 @@ pub fn create_round_handler(
         round: round.key(),
         round_id: round.id,
         payment_token: payment_token.key(),
+        kind,
         total_pool,
         amount_per_share,
-        snapshot_slot: clock.slot,
+        snapshot_slot,
+        claimable_from: round.claimable_from,
         expires_at: round.expires_at,
+        min_claim,
         created_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
@@ -126,8 +204,280 @@ pub fn create_round_handler(
     Ok(())
 }
 
+/// Check that `proposal` is an executed InitiateDividend proposal for this token, payment
+/// token, amount and round ID, then consume its action marker through the governance
+/// program. The marker can only be consumed by the proposal's executor, so `authority`
+/// must be them.
+fn consume_governance_approval(accounts: &CreateDividendRound, total_pool: u64, round_id: u64) -> Result<()> {
+    let (Some(proposal), Some(action_marker), Some(governance_program)) = (
+        accounts.proposal.as_ref(),
+        accounts.action_marker.as_ref(),
+        accounts.governance_program.as_ref(),
+    ) else {
+        return Err(TokenError::GovernanceApprovalRequired.into());
+    };
+
+    {
+        let data = proposal.try_borrow_data()?;
+        require!(data.len() >= governance_proposal::MIN_LEN, TokenError::GovernanceActionMismatch);
+        let discriminator = hash(b"account:Proposal").to_bytes();
+        let authorized = data[governance_proposal::DISCRIMINATOR] == discriminator[..8]
+            && data[governance_proposal::TOKEN_CONFIG] == accounts.token_config.key().to_bytes()
+            && data[governance_proposal::ACTION_TAG] == governance_proposal::INITIATE_DIVIDEND_TAG
+            && data[governance_proposal::PAYMENT_TOKEN] == accounts.payment_token.key().to_bytes()
+            && data[governance_proposal::TOTAL_AMOUNT] == total_pool.to_le_bytes()
+            && data[governance_proposal::ROUND_ID] == round_id.to_le_bytes();
+        require!(authorized, TokenError::GovernanceActionMismatch);
+    }
+
+    // consume_action_marker rejects a marker that is missing, already consumed,
+    // or belongs to another proposal
+    let ix = Instruction {
+        program_id: GOVERNANCE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(proposal.key(), false),
+            AccountMeta::new(action_marker.key(), false),
+            AccountMeta::new_readonly(accounts.authority.key(), true),
+        ],
+        data: hash(b"global:consume_action_marker").to_bytes()[..8].to_vec(),
+    };
+    invoke(
+        &ix,
+        &[
+            proposal.to_account_info(),
+            action_marker.to_account_info(),
+            accounts.authority.to_account_info(),
+            governance_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateDividendSnapshot<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.status == DividendStatus::Active @ TokenError::DividendNotActive,
+        constraint = dividend_round.claims_count == 0 @ TokenError::DividendSnapshotLocked,
+        constraint = dividend_round.created_by == authority.key() @ TokenError::Unauthorized,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Re-bind the round to the current total supply (e.g. after mints, burns or a split
+/// since creation) and recompute amount_per_share. Only allowed before the first claim.
+pub fn snapshot_handler(ctx: Context<CreateDividendSnapshot>) -> Result<()> {
+    let clock = Clock::get()?;
+    let supply = ctx.accounts.token_config.total_supply;
+    require!(supply > 0, TokenError::NoEntitlement);
+
+    let round = &mut ctx.accounts.dividend_round;
+    let amount_per_share = calculate_amount_per_share(round.total_pool, supply)?;
+
+    round.snapshot_slot = clock.slot;
+    round.supply_at_snapshot = supply;
+    round.amount_per_share = amount_per_share;
+    round.dust = round.total_pool.saturating_sub(calculate_entitlement(supply, amount_per_share));
+
+    emit!(DividendSnapshotTaken {
+        token_config: ctx.accounts.token_config.key(),
+        round: round.key(),
+        supply_at_snapshot: supply,
+        amount_per_share,
+        snapshot_slot: clock.slot,
+        taken_by: ctx.accounts.authority.key(),
+    });
+
+    msg!("Dividend round {} snapshot: supply {}, {} per share", round.id, supply, amount_per_share);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendDividendExpiry<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.status == DividendStatus::Active @ TokenError::DividendNotActive,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Token admin multisig - required unless the authority created the round
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+    )]
+    pub multisig: Option<Account<'info, MultisigConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Push back the claim deadline of an open round. The new expiry must be in the
+/// future and later than the current one; rounds without an expiry cannot be extended.
+/// Callable by the round's creator or a token multisig signer.
+pub fn extend_expiry_handler(ctx: Context<ExtendDividendExpiry>, new_expiry: i64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let is_admin = ctx.accounts.multisig
+        .as_ref()
+        .is_some_and(|m| m.signers.contains(&authority));
+    require!(
+        ctx.accounts.dividend_round.created_by == authority || is_admin,
+        TokenError::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    let round = &mut ctx.accounts.dividend_round;
+
+    let old_expiry = round.expires_at.ok_or(TokenError::InvalidDividendExpiry)?;
+    require!(new_expiry > old_expiry, TokenError::InvalidDividendExpiry);
+    require!(new_expiry > clock.unix_timestamp, TokenError::InvalidDividendExpiry);
+
+    round.expires_at = Some(new_expiry);
+
+    emit!(DividendExpiryExtended {
+        token_config: ctx.accounts.token_config.key(),
+        round: round.key(),
+        round_id: round.id,
+        old_expiry,
+        new_expiry,
+        extended_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Extended dividend round {} expiry from {} to {}", round.id, old_expiry, new_expiry);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TopUpDividendRound<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.status == DividendStatus::Active @ TokenError::DividendNotActive,
+        constraint = !dividend_round.dust_swept @ TokenError::DividendDustAlreadySwept,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Payment token mint
+    #[account(
+        constraint = payment_token.key() == dividend_round.payment_token @ TokenError::Unauthorized,
+    )]
+    pub payment_token: InterfaceAccount<'info, Mint>,
+
+    /// Source token account (authority's account with the additional funds)
+    #[account(
+        mut,
+        token::mint = payment_token,
+        token::authority = authority,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Dividend pool PDA token account
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_POOL_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump,
+        token::mint = payment_token,
+        token::authority = dividend_round,
+    )]
+    pub dividend_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token admin multisig - required unless the authority created the round
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+    )]
+    pub multisig: Option<Account<'info, MultisigConfig>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Add funds to an active round. amount_per_share is recomputed over the snapshot
+/// supply, so holders who already claimed can claim again for the per-share increase.
+pub fn top_up_handler(ctx: Context<TopUpDividendRound>, amount: u64) -> Result<()> {
+    require!(amount > 0, TokenError::InvalidAmount);
+    let authority = ctx.accounts.authority.key();
+    let is_admin = ctx.accounts.multisig
+        .as_ref()
+        .is_some_and(|m| m.signers.contains(&authority));
+    require!(
+        ctx.accounts.dividend_round.created_by == authority || is_admin,
+        TokenError::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    if let Some(expires_at) = ctx.accounts.dividend_round.expires_at {
+        require!(clock.unix_timestamp <= expires_at, TokenError::DividendExpired);
+    }
+
+    let round = &mut ctx.accounts.dividend_round;
+    round.total_pool = round.total_pool
+        .checked_add(amount)
+        .ok_or(TokenError::MathOverflow)?;
+    round.amount_per_share = calculate_amount_per_share(round.total_pool, round.supply_at_snapshot)?;
+    round.dust = round.total_pool
+        .saturating_sub(calculate_entitlement(round.supply_at_snapshot, round.amount_per_share));
+
+    let payment_token = &ctx.accounts.payment_token;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.source_token_account.to_account_info(),
+        mint: payment_token.to_account_info(),
+        to: ctx.accounts.dividend_pool.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_2022::transfer_checked(cpi_ctx, amount, payment_token.decimals)?;
+
+    emit!(DividendRoundToppedUp {
+        token_config: ctx.accounts.token_config.key(),
+        round: round.key(),
+        amount,
+        new_total_pool: round.total_pool,
+        new_amount_per_share: round.amount_per_share,
+        topped_up_by: authority,
+        slot: clock.slot,
+    });
+
+    msg!("Topped up dividend round {} by {}", round.id, amount);
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct ClaimDividend<'info> {
+    #[account(
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -143,7 +493,7 @@ pub struct ClaimDividend<'info> {
     pub dividend_round: Account<'info, DividendRound>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = claimant,
         space = DividendClaim::LEN,
         seeds = [
@@ -155,6 +505,15 @@ pub struct ClaimDividend<'info> {
     )]
     pub dividend_claim: Account<'info, DividendClaim>,
 
+    /// Claimant must be currently approved to receive dividends
+    #[account(
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), claimant.key().as_ref()],
+        bump = claimant_allowlist.bump,
+        constraint = require_active_allowlist(&claimant_allowlist, &token_config.key(), &claimant.key()).is_ok() @ TokenError::RecipientNotApproved,
+        constraint = !claimant_allowlist.dividend_opt_out @ TokenError::DividendOptedOut,
+    )]
+    pub claimant_allowlist: Box<Account<'info, AllowlistEntry>>,
+
     /// Claimant's equity token account (to verify holdings at snapshot)
     #[account(
         token::mint = token_config.mint,
@@ -198,19 +557,36 @@ pub fn claim_handler(ctx: Context<ClaimDividend>) -> Result<()> {
     let clock = Clock::get()?;
     let round = &ctx.accounts.dividend_round;
     let token_config = &ctx.accounts.token_config;
-    let payment_token = &ctx.accounts.payment_token;
 
-    // Check expiration
+    // Check claim window
+    if let Some(claimable_from) = round.claimable_from {
+        require!(clock.unix_timestamp >= claimable_from, TokenError::DividendNotYetClaimable);
+    }
     if let Some(expires_at) = round.expires_at {
         require!(clock.unix_timestamp <= expires_at, TokenError::DividendExpired);
     }
 
-    // Get claimant's balance (in production, would use snapshot from snapshot_slot)
-    let balance = ctx.accounts.claimant_equity_account.amount;
+    // Get claimant's balance (in production, would use snapshot from snapshot_slot).
+    // No single holder can be entitled to more than the snapshot supply. A repeat
+    // claim is paid on the balance recorded at the first claim, so shares bought
+    // since then cannot collect a dividend another holder already received.
+    let is_first_claim = ctx.accounts.dividend_claim.wallet == Pubkey::default();
+    let balance = if is_first_claim {
+        ctx.accounts.claimant_equity_account.amount.min(round.supply_at_snapshot)
+    } else {
+        ctx.accounts.dividend_claim.balance
+    };
     require!(balance > 0, TokenError::NoEntitlement);
 
-    // Calculate entitlement
-    let entitlement = (balance as u128 * round.amount_per_share as u128 / 1_000_000) as u64;
+    // Calculate entitlement, paying only what hasn't been claimed yet
+    // (the pool may have been topped up since a previous claim)
+    // Holders owed less than min_claim can't claim, so no rent is spent on a claim
+    // account for dust; their share stays in the pool for the close sweep
+    let gross_entitlement = calculate_entitlement(balance, round.amount_per_share);
+    require!(gross_entitlement >= round.min_claim, TokenError::BelowMinimumClaim);
+
+    let already_claimed = ctx.accounts.dividend_claim.claimed;
+    let entitlement = gross_entitlement.saturating_sub(already_claimed);
     require!(entitlement > 0, TokenError::NoEntitlement);
 
     // Ensure pool has enough funds
@@ -219,39 +595,68 @@ pub fn claim_handler(ctx: Context<ClaimDividend>) -> Result<()> {
         TokenError::InsufficientPoolFunds
     );
 
-    // Record claim
+    // Ensure claims never exceed the funded pool (less any dust already swept)
+    let new_total_claimed = round.total_claimed
+        .checked_add(entitlement)
+        .ok_or(TokenError::MathOverflow)?;
+    let claimable_pool = if round.dust_swept {
+        round.total_pool.saturating_sub(round.dust)
+    } else {
+        round.total_pool
+    };
+    require!(new_total_claimed <= claimable_pool, TokenError::InsufficientPoolFunds);
+
+    let round_key = round.key();
+    let round_id_bytes = round.id.to_le_bytes();
+    let round_bump = round.bump;
+    let token_config_key = token_config.key();
+
+    // Commit all claim state before the external transfer. If the CPI fails the
+    // whole instruction reverts, so no partial DividendClaim can persist.
     let claim = &mut ctx.accounts.dividend_claim;
-    claim.round = round.key();
-    claim.wallet = ctx.accounts.claimant.key();
-    claim.amount = entitlement;
+    if is_first_claim {
+        claim.round = round_key;
+        claim.wallet = ctx.accounts.claimant.key();
+        claim.balance = balance;
+        claim.bump = ctx.bumps.dividend_claim;
+    }
+    claim.claimed = already_claimed
+        .checked_add(entitlement)
+        .ok_or(TokenError::MathOverflow)?;
     claim.claimed_at = clock.unix_timestamp;
-    claim.bump = ctx.bumps.dividend_claim;
+
+    let round = &mut ctx.accounts.dividend_round;
+    round.total_claimed = new_total_claimed;
+    if is_first_claim {
+        round.claims_count = round.claims_count
+            .checked_add(1)
+            .ok_or(TokenError::MathOverflow)?;
+    }
 
     // Transfer from dividend pool to claimant using PDA signing
-    let token_config_key = token_config.key();
-    let round_id_bytes = round.id.to_le_bytes();
     let seeds = &[
         DIVIDEND_ROUND_SEED,
         token_config_key.as_ref(),
         &round_id_bytes,
-        &[round.bump],
+        &[round_bump],
     ];
     let signer_seeds = &[&seeds[..]];
 
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.dividend_pool.to_account_info(),
-        mint: payment_token.to_account_info(),
+        mint: ctx.accounts.payment_token.to_account_info(),
         to: ctx.accounts.claimant_payment_account.to_account_info(),
         authority: ctx.accounts.dividend_round.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token_2022::transfer_checked(cpi_ctx, entitlement, payment_token.decimals)?;
+    token_2022::transfer_checked(cpi_ctx, entitlement, ctx.accounts.payment_token.decimals)?;
 
     emit!(DividendClaimed {
-        token_config: token_config.key(),
-        round: round.key(),
+        token_config: token_config_key,
+        round: round_key,
         wallet: ctx.accounts.claimant.key(),
+        kind: ctx.accounts.dividend_round.kind.clone(),
         amount: entitlement,
         slot: clock.slot,
     });
@@ -260,3 +665,490 @@ pub fn claim_handler(ctx: Context<ClaimDividend>) -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct SweepDividendDust<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.created_by == authority.key() @ TokenError::Unauthorized,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Payment token mint
+    #[account(
+        constraint = payment_token.key() == dividend_round.payment_token @ TokenError::Unauthorized,
+    )]
+    pub payment_token: InterfaceAccount<'info, Mint>,
+
+    /// Dividend pool PDA token account
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_POOL_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump,
+        token::mint = dividend_round.payment_token,
+        token::authority = dividend_round,
+    )]
+    pub dividend_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury account to receive the dust
+    #[account(
+        mut,
+        token::mint = dividend_round.payment_token,
+        token::authority = token_config.treasury,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Sweep the rounding remainder of a round's pool (unclaimable by any holder) to treasury
+pub fn sweep_dust_handler(ctx: Context<SweepDividendDust>) -> Result<()> {
+    let clock = Clock::get()?;
+    let round = &ctx.accounts.dividend_round;
+
+    require!(!round.dust_swept && round.dust > 0, TokenError::NoDustToSweep);
+
+    let dust = round.dust;
+    let token_config_key = ctx.accounts.token_config.key();
+    let round_id_bytes = round.id.to_le_bytes();
+    let seeds = &[
+        DIVIDEND_ROUND_SEED,
+        token_config_key.as_ref(),
+        &round_id_bytes,
+        &[round.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.dividend_pool.to_account_info(),
+        mint: ctx.accounts.payment_token.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        authority: ctx.accounts.dividend_round.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token_2022::transfer_checked(cpi_ctx, dust, ctx.accounts.payment_token.decimals)?;
+
+    let round = &mut ctx.accounts.dividend_round;
+    round.dust_swept = true;
+
+    emit!(DividendDustSwept {
+        token_config: token_config_key,
+        round: round.key(),
+        amount: dust,
+        swept_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Swept {} dividend dust from round {}", dust, round.id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredDividend<'info> {
+    #[account(
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.status == DividendStatus::Active @ TokenError::DividendNotActive,
+        constraint = dividend_round.created_by == authority.key() @ TokenError::Unauthorized,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Payment token mint
+    #[account(
+        constraint = payment_token.key() == dividend_round.payment_token @ TokenError::Unauthorized,
+    )]
+    pub payment_token: InterfaceAccount<'info, Mint>,
+
+    /// Dividend pool PDA token account
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_POOL_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump,
+        token::mint = dividend_round.payment_token,
+        token::authority = dividend_round,
+    )]
+    pub dividend_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury account to receive the unclaimed funds
+    #[account(
+        mut,
+        token::mint = dividend_round.payment_token,
+        token::authority = token_config.treasury,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Return the unclaimed remainder of an expired round to treasury, keeping the round
+/// account as a record. The round is marked Completed before any funds move, so no
+/// claim can succeed afterwards and a second reclaim is rejected.
+pub fn reclaim_expired_handler(ctx: Context<ReclaimExpiredDividend>) -> Result<()> {
+    let clock = Clock::get()?;
+    let round = &mut ctx.accounts.dividend_round;
+
+    let expired = round.expires_at.is_some_and(|expires_at| clock.unix_timestamp > expires_at);
+    require!(expired, TokenError::DividendRoundStillActive);
+
+    round.status = DividendStatus::Completed;
+
+    // Unclaimed = pool - claimed, less any dust already swept out of the pool
+    let swept_dust = if round.dust_swept { round.dust } else { 0 };
+    let reclaimable = round.total_pool
+        .checked_sub(round.total_claimed)
+        .and_then(|remaining| remaining.checked_sub(swept_dust))
+        .ok_or(TokenError::MathOverflow)?;
+
+    let round_id = round.id;
+    let round_bump = round.bump;
+    let round_key = round.key();
+
+    if reclaimable > 0 {
+        let token_config_key = ctx.accounts.token_config.key();
+        let round_id_bytes = round_id.to_le_bytes();
+        let seeds = &[
+            DIVIDEND_ROUND_SEED,
+            token_config_key.as_ref(),
+            &round_id_bytes,
+            &[round_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.dividend_pool.to_account_info(),
+            mint: ctx.accounts.payment_token.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.dividend_round.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_2022::transfer_checked(cpi_ctx, reclaimable, ctx.accounts.payment_token.decimals)?;
+    }
+
+    emit!(DividendReclaimed {
+        token_config: ctx.accounts.token_config.key(),
+        round: round_key,
+        round_id,
+        amount: reclaimable,
+        reclaimed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Reclaimed {} unclaimed dividend from expired round {}", reclaimable, round_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseDividendRound<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+        constraint = dividend_round.created_by == authority.key() @ TokenError::Unauthorized,
+        close = authority,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Payment token mint
+    #[account(
+        constraint = payment_token.key() == dividend_round.payment_token @ TokenError::Unauthorized,
+    )]
+    pub payment_token: InterfaceAccount<'info, Mint>,
+
+    /// Dividend pool PDA token account
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_POOL_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump,
+        token::mint = dividend_round.payment_token,
+        token::authority = dividend_round,
+    )]
+    pub dividend_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury account to receive any unclaimed funds (required if the pool is not empty)
+    #[account(
+        mut,
+        token::mint = dividend_round.payment_token,
+        token::authority = token_config.treasury,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn close_round_handler(ctx: Context<CloseDividendRound>) -> Result<()> {
+    let clock = Clock::get()?;
+    let round = &ctx.accounts.dividend_round;
+
+    // Only expired or fully distributed rounds can be closed
+    let expired = round.expires_at.is_some_and(|expires_at| clock.unix_timestamp > expires_at);
+    let residual = ctx.accounts.dividend_pool.amount;
+    require!(expired || residual == 0, TokenError::DividendRoundStillActive);
+
+    let token_config_key = ctx.accounts.token_config.key();
+    let round_id_bytes = round.id.to_le_bytes();
+    let seeds = &[
+        DIVIDEND_ROUND_SEED,
+        token_config_key.as_ref(),
+        &round_id_bytes,
+        &[round.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Sweep unclaimed funds to treasury before the round (pool authority) is closed
+    if residual > 0 {
+        let treasury = ctx.accounts.treasury_token_account
+            .as_ref()
+            .ok_or(TokenError::TreasuryRequired)?;
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.dividend_pool.to_account_info(),
+            mint: ctx.accounts.payment_token.to_account_info(),
+            to: treasury.to_account_info(),
+            authority: ctx.accounts.dividend_round.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_2022::transfer_checked(cpi_ctx, residual, ctx.accounts.payment_token.decimals)?;
+    }
+
+    // The emptied pool would otherwise be stranded once its authority is gone
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.dividend_pool.to_account_info(),
+        destination: ctx.accounts.authority.to_account_info(),
+        authority: ctx.accounts.dividend_round.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token_2022::close_account(cpi_ctx)?;
+
+    let round = &mut ctx.accounts.dividend_round;
+    round.status = DividendStatus::Completed;
+
+    emit!(DividendRoundClosed {
+        token_config: ctx.accounts.token_config.key(),
+        round: round.key(),
+        round_id: round.id,
+        residual_swept: residual,
+        closed_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Closed dividend round {} (swept {} residual)", round.id, residual);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseDividendClaim<'info> {
+    /// CHECK: The claim's round. Either already closed by close_dividend_round, or a
+    /// DividendRound whose status is checked in the handler.
+    #[account(
+        constraint = dividend_round.key() == dividend_claim.round @ TokenError::Unauthorized,
+    )]
+    pub dividend_round: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            DIVIDEND_CLAIM_SEED,
+            dividend_round.key().as_ref(),
+            claimant.key().as_ref()
+        ],
+        bump = dividend_claim.bump,
+        constraint = dividend_claim.wallet == claimant.key() @ TokenError::Unauthorized,
+        close = claimant,
+    )]
+    pub dividend_claim: Account<'info, DividendClaim>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+}
+
+/// Close the claimant's own claim record and reclaim its rent once the round is
+/// Completed. close_dividend_round deletes the round account, so a missing round
+/// also counts as completed.
+pub fn close_claim_handler(ctx: Context<CloseDividendClaim>) -> Result<()> {
+    let round_info = ctx.accounts.dividend_round.to_account_info();
+    if !round_info.data_is_empty() {
+        let round: Account<DividendRound> = Account::try_from(&round_info)?;
+        require!(round.status == DividendStatus::Completed, TokenError::DividendRoundStillActive);
+    }
+
+    let clock = Clock::get()?;
+
+    emit!(DividendClaimClosed {
+        round: ctx.accounts.dividend_round.key(),
+        wallet: ctx.accounts.claimant.key(),
+        claimed: ctx.accounts.dividend_claim.claimed,
+        slot: clock.slot,
+    });
+
+    msg!("Closed dividend claim for {}", ctx.accounts.claimant.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetClaimableDividend<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [
+            DIVIDEND_ROUND_SEED,
+            token_config.key().as_ref(),
+            &dividend_round.id.to_le_bytes()
+        ],
+        bump = dividend_round.bump,
+    )]
+    pub dividend_round: Account<'info, DividendRound>,
+
+    /// Claimant's equity token account
+    #[account(
+        token::mint = token_config.mint,
+    )]
+    pub claimant_equity_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Claimant's existing claim record, if they have claimed before
+    #[account(
+        constraint = dividend_claim.round == dividend_round.key() @ TokenError::Unauthorized,
+        constraint = dividend_claim.wallet == claimant_equity_account.owner @ TokenError::Unauthorized,
+    )]
+    pub dividend_claim: Option<Account<'info, DividendClaim>>,
+}
+
+/// Read-only: returns the claimant's unclaimed entitlement for this round via return data.
+/// Returns 0 if the round is not active, not yet claimable, or has expired, or if the
+/// entitlement is below the round's min_claim.
+pub fn get_claimable_handler(ctx: Context<GetClaimableDividend>) -> Result<()> {
+    let clock = Clock::get()?;
+    let round = &ctx.accounts.dividend_round;
+
+    let not_yet_open = round.claimable_from.is_some_and(|from| clock.unix_timestamp < from);
+    let expired = round.expires_at.is_some_and(|expires_at| clock.unix_timestamp > expires_at);
+    let claimable = if round.status == DividendStatus::Active && !not_yet_open && !expired {
+        let already_claimed = ctx.accounts.dividend_claim.as_ref().map_or(0, |claim| claim.claimed);
+        let balance = ctx.accounts.claimant_equity_account.amount.min(round.supply_at_snapshot);
+        let gross_entitlement = calculate_entitlement(balance, round.amount_per_share);
+        if gross_entitlement < round.min_claim {
+            0
+        } else {
+            gross_entitlement.saturating_sub(already_claimed)
+        }
+    } else {
+        0
+    };
+
+    set_return_data(&claimable.to_le_bytes());
+
+    Ok(())
+}
+
+/// Per-share payout scaled by SHARE_PRECISION. Rejects a pool so small relative to
+/// supply that every share would round down to nothing.
+pub fn calculate_amount_per_share(total_pool: u64, supply: u64) -> Result<u64> {
+    require!(supply > 0, TokenError::NoEntitlement);
+    let scaled = total_pool as u128 * SHARE_PRECISION / supply as u128;
+    require!(scaled > 0, TokenError::DividendPerShareZero);
+    u64::try_from(scaled).map_err(|_| TokenError::MathOverflow.into())
+}
+
+/// Calculate dividend entitlement for a balance (amount_per_share is scaled by SHARE_PRECISION)
+pub fn calculate_entitlement(balance: u64, amount_per_share: u64) -> u64 {
+    (balance as u128 * amount_per_share as u128 / SHARE_PRECISION) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entitlement_rounds_down_to_whole_units() {
+        // 10 over 3 shares: 3.333333 per share at SHARE_PRECISION
+        let amount_per_share = calculate_amount_per_share(10, 3).unwrap();
+        assert_eq!(amount_per_share, 3_333_333);
+        assert_eq!(calculate_entitlement(0, amount_per_share), 0);
+        assert_eq!(calculate_entitlement(1, amount_per_share), 3);
+        assert_eq!(calculate_entitlement(3, amount_per_share), 9);
+    }
+
+    #[test]
+    fn entitlement_of_the_full_supply_never_exceeds_the_pool() {
+        for (total_pool, supply) in [(1_000_000, 3), (7, 1_000_000), (u64::MAX / 2, u64::MAX / 3)] {
+            let amount_per_share = calculate_amount_per_share(total_pool, supply).unwrap();
+            assert!(calculate_entitlement(supply, amount_per_share) <= total_pool);
+        }
+    }
+
+    #[test]
+    fn amount_per_share_rejects_empty_supply_and_zero_rounds() {
+        assert!(calculate_amount_per_share(1_000, 0).is_err());
+        // 1 unit over 2M shares rounds to zero per share
+        assert!(calculate_amount_per_share(1, 2_000_000).is_err());
+    }
+
+    #[test]
+    fn claims_across_holders_leave_at_most_supply_units_of_dust() {
+        let balances = [1u64, 7, 333, 12_345, 987_654];
+        let supply: u64 = balances.iter().sum();
+        let total_pool = 1_000_003;
+
+        let amount_per_share = calculate_amount_per_share(total_pool, supply).unwrap();
+        let claimed: u64 = balances
+            .iter()
+            .map(|&balance| calculate_entitlement(balance, amount_per_share))
+            .sum();
+
+        assert!(claimed <= total_pool);
+        assert!(total_pool - claimed <= supply);
+        // The round's recorded dust never exceeds what the holders leave behind
+        let dust = total_pool - calculate_entitlement(supply, amount_per_share);
+        assert!(dust <= total_pool - claimed);
+    }
+}
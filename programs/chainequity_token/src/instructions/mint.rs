@@ -3,13 +3,17 @@ use anchor_spl::token_2022::{self, Token2022, MintTo};
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use chainequity_factory::instructions::create_token::TokenConfig;
 
-use crate::state::{AllowlistEntry, AllowlistStatus, MintAuthority, ALLOWLIST_SEED, MINT_AUTHORITY_SEED};
+use crate::state::{AllowlistEntry, MintAuthority, WalletRestrictions, ALLOWLIST_SEED, MINT_AUTHORITY_SEED, RESTRICTIONS_SEED};
 use crate::errors::TokenError;
 use crate::events::TokensMinted;
+use crate::instructions::allowlist::require_active_allowlist;
 
 #[derive(Accounts)]
 pub struct MintTokens<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+    )]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -24,15 +28,25 @@ pub struct MintTokens<'info> {
         bump = mint_authority.bump,
         constraint = mint_authority.token_config == token_config.key() @ TokenError::Unauthorized,
         constraint = mint_authority.mint == mint.key() @ TokenError::Unauthorized,
+        constraint = mint_authority.authority_transferred @ TokenError::MintAuthorityNotInitialized,
     )]
     pub mint_authority: Account<'info, MintAuthority>,
 
+    /// Required unless the recipient is the token's designated treasury
     #[account(
         seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), recipient.key().as_ref()],
         bump = recipient_allowlist.bump,
-        constraint = recipient_allowlist.status == AllowlistStatus::Active @ TokenError::RecipientNotApproved,
+        constraint = require_active_allowlist(&recipient_allowlist, &token_config.key(), &recipient.key()).is_ok() @ TokenError::RecipientNotApproved,
     )]
-    pub recipient_allowlist: Account<'info, AllowlistEntry>,
+    pub recipient_allowlist: Option<Account<'info, AllowlistEntry>>,
+
+    /// CHECK: Recipient's restrictions PDA, so issuance respects max_balance.
+    /// May be uninitialized if the wallet has no restrictions.
+    #[account(
+        seeds = [RESTRICTIONS_SEED, token_config.key().as_ref(), recipient.key().as_ref()],
+        bump,
+    )]
+    pub recipient_restrictions: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -53,6 +67,31 @@ pub struct MintTokens<'info> {
 pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
     require!(amount > 0, TokenError::InvalidAmount);
     require!(!ctx.accounts.token_config.is_paused, TokenError::TransfersPaused);
+    // Fail with a clear error instead of an opaque Token-2022 CPI failure
+    require!(!ctx.accounts.recipient_token_account.is_frozen(), TokenError::AccountFrozen);
+
+    // Only the designated treasury may hold authorized-but-unissued shares without an
+    // allowlist entry; every other recipient must be active on the allowlist
+    let is_treasury = ctx.accounts.token_config.treasury != Pubkey::default()
+        && ctx.accounts.recipient.key() == ctx.accounts.token_config.treasury;
+    require!(
+        is_treasury || ctx.accounts.recipient_allowlist.is_some(),
+        TokenError::RecipientNotApproved
+    );
+
+    // Primary issuance must respect the same concentration limit as transfers
+    let restrictions_info = ctx.accounts.recipient_restrictions.to_account_info();
+    if !restrictions_info.data_is_empty() {
+        let restrictions: Account<'_, WalletRestrictions> = Account::try_from(&restrictions_info)?;
+        if let Some(max_balance) = restrictions.max_balance {
+            let new_balance = ctx.accounts.recipient_token_account.amount
+                .checked_add(amount)
+                .ok_or(TokenError::MathOverflow)?;
+            require!(new_balance <= max_balance, TokenError::MaxBalanceExceeded);
+        }
+    }
+
+    let is_new_holder = ctx.accounts.recipient_token_account.amount == 0;
 
     let token_config = &ctx.accounts.token_config;
     let mint_authority = &ctx.accounts.mint_authority;
@@ -86,6 +125,13 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         .checked_add(amount)
         .ok_or(TokenError::MathOverflow)?;
 
+    // Track first-time holders
+    if is_new_holder {
+        token_config.holder_count = token_config.holder_count
+            .checked_add(1)
+            .ok_or(TokenError::MathOverflow)?;
+    }
+
     let clock = Clock::get()?;
 
     emit!(TokensMinted {
@@ -101,3 +147,156 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct MintTokensBatch<'info> {
+    #[account(
+        mut,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The mint authority PDA that can sign for minting
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, token_config.key().as_ref()],
+        bump = mint_authority.bump,
+        constraint = mint_authority.token_config == token_config.key() @ TokenError::Unauthorized,
+        constraint = mint_authority.mint == mint.key() @ TokenError::Unauthorized,
+        constraint = mint_authority.authority_transferred @ TokenError::MintAuthorityNotInitialized,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    // Remaining accounts: (recipient_allowlist, recipient_token_account, recipient_restrictions)
+    // per amount, in order. recipient_restrictions is the wallet's restrictions PDA, which
+    // may be uninitialized if the wallet has none.
+}
+
+pub fn batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintTokensBatch<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    require!(!ctx.accounts.token_config.is_paused, TokenError::TransfersPaused);
+    require!(!amounts.is_empty(), TokenError::InvalidAmount);
+    require!(amounts.len() <= MintAuthority::MAX_MINT_BATCH, TokenError::MintBatchTooLarge);
+    require!(
+        ctx.remaining_accounts.len() == amounts.len() * 3,
+        TokenError::InvalidRemainingAccounts
+    );
+
+    let token_config_key = ctx.accounts.token_config.key();
+    let mint_key = ctx.accounts.mint.key();
+    let bump = ctx.accounts.mint_authority.bump;
+
+    // Use mint_authority PDA for signing - this PDA is owned by the token program
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        token_config_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let clock = Clock::get()?;
+    let mut total_minted: u64 = 0;
+    let mut new_holders: u32 = 0;
+    let mut seen_token_accounts: Vec<Pubkey> = Vec::with_capacity(amounts.len());
+
+    for (accounts, &amount) in ctx.remaining_accounts.chunks(3).zip(amounts.iter()) {
+        require!(amount > 0, TokenError::InvalidAmount);
+
+        // Recipient must be an Active allowlist entry for this token
+        let allowlist: Account<'info, AllowlistEntry> = Account::try_from(&accounts[0])?;
+        let recipient = allowlist.wallet;
+        let expected_allowlist = Pubkey::create_program_address(
+            &[ALLOWLIST_SEED, token_config_key.as_ref(), recipient.as_ref(), &[allowlist.bump]],
+            ctx.program_id,
+        ).map_err(|_| error!(TokenError::RecipientNotApproved))?;
+        require_keys_eq!(allowlist.key(), expected_allowlist, TokenError::RecipientNotApproved);
+        require!(
+            require_active_allowlist(&allowlist, &token_config_key, &recipient).is_ok(),
+            TokenError::RecipientNotApproved
+        );
+
+        let recipient_token_account: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&accounts[1])?;
+        require_keys_eq!(recipient_token_account.mint, mint_key, TokenError::InvalidRemainingAccounts);
+        require_keys_eq!(recipient_token_account.owner, recipient, TokenError::InvalidRemainingAccounts);
+        require!(!recipient_token_account.is_frozen(), TokenError::AccountFrozen);
+
+        // A repeated token account would be counted as a new holder more than once
+        require!(
+            !seen_token_accounts.contains(&recipient_token_account.key()),
+            TokenError::InvalidRemainingAccounts
+        );
+        seen_token_accounts.push(recipient_token_account.key());
+
+        // Primary issuance must respect the same concentration limit as transfers
+        let (expected_restrictions, _) = Pubkey::find_program_address(
+            &[RESTRICTIONS_SEED, token_config_key.as_ref(), recipient.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(accounts[2].key(), expected_restrictions, TokenError::InvalidRemainingAccounts);
+        if !accounts[2].data_is_empty() {
+            let restrictions: Account<'info, WalletRestrictions> = Account::try_from(&accounts[2])?;
+            if let Some(max_balance) = restrictions.max_balance {
+                let new_balance = recipient_token_account.amount
+                    .checked_add(amount)
+                    .ok_or(TokenError::MathOverflow)?;
+                require!(new_balance <= max_balance, TokenError::MaxBalanceExceeded);
+            }
+        }
+
+        if recipient_token_account.amount == 0 {
+            new_holders = new_holders.checked_add(1).ok_or(TokenError::MathOverflow)?;
+        }
+
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        total_minted = total_minted
+            .checked_add(amount)
+            .ok_or(TokenError::MathOverflow)?;
+
+        emit!(TokensMinted {
+            token_config: token_config_key,
+            to: recipient,
+            amount,
+            new_total_supply: ctx.accounts.token_config.total_supply
+                .checked_add(total_minted)
+                .ok_or(TokenError::MathOverflow)?,
+            minted_by: ctx.accounts.authority.key(),
+            slot: clock.slot,
+        });
+    }
+
+    // Update total supply and holder count once for the whole batch
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.total_supply = token_config.total_supply
+        .checked_add(total_minted)
+        .ok_or(TokenError::MathOverflow)?;
+    token_config.holder_count = token_config.holder_count
+        .checked_add(new_holders)
+        .ok_or(TokenError::MathOverflow)?;
+
+    msg!("Batch minted {} tokens to {} recipients", total_minted, amounts.len());
+
+    Ok(())
+}
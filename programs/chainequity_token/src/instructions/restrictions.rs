@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
+use chainequity_factory::state::{MultiSig, MultiSigTransaction, TransactionType};
 
-use crate::state::{WalletRestrictions, RESTRICTIONS_SEED};
+use crate::state::{AdminAction, AuditLog, WalletRestrictions, AUDIT_LOG_SEED, RESTRICTIONS_SEED};
 use crate::errors::TokenError;
-use crate::events::WalletRestrictionsUpdated;
+use crate::events::{WalletRestrictionsUpdated, WalletRestrictionsCleared, KycLevelLimitsUpdated};
+use crate::instructions::admin::record_admin_action;
 
 #[derive(Accounts)]
 pub struct SetWalletRestrictions<'info> {
@@ -35,20 +37,46 @@ pub fn set_handler(
     daily_limit: Option<u64>,
     lockout_until: Option<i64>,
     max_balance: Option<u64>,
+    min_transfer_interval: Option<u64>,
 ) -> Result<()> {
+    validate_restrictions(&ctx.accounts.token_config, daily_limit, max_balance)?;
+
     let restrictions = &mut ctx.accounts.wallet_restrictions;
     let clock = Clock::get()?;
 
+    // An existing account with any limit set that is now being set to all-None
+    let had_restrictions = restrictions.bump != 0 && (
+        restrictions.daily_transfer_limit.is_some()
+            || restrictions.lockout_until.is_some()
+            || restrictions.max_balance.is_some()
+            || restrictions.min_transfer_interval.is_some()
+    );
+    let clearing = had_restrictions
+        && daily_limit.is_none()
+        && lockout_until.is_none()
+        && max_balance.is_none()
+        && min_transfer_interval.is_none();
+    let previous = (
+        restrictions.daily_transfer_limit,
+        restrictions.lockout_until,
+        restrictions.max_balance,
+        restrictions.min_transfer_interval,
+    );
+
     restrictions.token_config = ctx.accounts.token_config.key();
     restrictions.wallet = ctx.accounts.wallet.key();
     restrictions.daily_transfer_limit = daily_limit;
     restrictions.lockout_until = lockout_until;
+    restrictions.lockout_notified = false;
     restrictions.max_balance = max_balance;
+    restrictions.min_transfer_interval = min_transfer_interval;
 
     // Initialize tracking if new
     if restrictions.bump == 0 {
         restrictions.transferred_today = 0;
         restrictions.last_transfer_day = clock.unix_timestamp;
+        restrictions.last_transfer_ts = 0;
+        restrictions.last_multisig_tx = None;
         restrictions.bump = ctx.bumps.wallet_restrictions;
     }
 
@@ -58,11 +86,191 @@ pub fn set_handler(
         daily_limit,
         lockout_until,
         max_balance,
+        min_transfer_interval,
         updated_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
 
+    if clearing {
+        emit!(WalletRestrictionsCleared {
+            token_config: ctx.accounts.token_config.key(),
+            wallet: ctx.accounts.wallet.key(),
+            previous_daily_limit: previous.0,
+            previous_lockout_until: previous.1,
+            previous_max_balance: previous.2,
+            previous_min_transfer_interval: previous.3,
+            cleared_by: ctx.accounts.authority.key(),
+            slot: clock.slot,
+        });
+    }
+
     msg!("Updated restrictions for wallet: {}", ctx.accounts.wallet.key());
 
     Ok(())
 }
+
+/// A zero daily limit is a freeze in disguise (use lockout_until), and limits above
+/// the total supply can never bind
+fn validate_restrictions(
+    token_config: &TokenConfig,
+    daily_limit: Option<u64>,
+    max_balance: Option<u64>,
+) -> Result<()> {
+    let total_supply = token_config.total_supply;
+    if let Some(limit) = daily_limit {
+        require!(limit > 0 && limit <= total_supply, TokenError::InvalidRestriction);
+    }
+    if let Some(max) = max_balance {
+        require!(max > 0 && max <= total_supply, TokenError::InvalidRestriction);
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetWalletRestrictionsMultisig<'info> {
+    #[account(
+        constraint = token_config.features.transfer_restrictions_enabled @ TokenError::FeatureDisabled,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Factory multi-sig controlling this token's mint
+    #[account(
+        constraint = multisig.token_mint == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultiSig>,
+
+    /// Executed UpdateRestrictions transaction authorizing this change
+    #[account(
+        constraint = transaction.multisig == multisig.key() @ TokenError::InvalidMultisigTransaction,
+        constraint = transaction.executed @ TokenError::InvalidMultisigTransaction,
+    )]
+    pub transaction: Account<'info, MultiSigTransaction>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = WalletRestrictions::LEN,
+        seeds = [RESTRICTIONS_SEED, token_config.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_restrictions: Account<'info, WalletRestrictions>,
+
+    /// CHECK: Wallet to set restrictions for (must match the transaction's address)
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Apply an executed factory multisig UpdateRestrictions transaction.
+/// max_balance and min_transfer_interval are not part of the transaction and are left unchanged.
+pub fn set_multisig_handler(ctx: Context<SetWalletRestrictionsMultisig>) -> Result<()> {
+    let transaction = &ctx.accounts.transaction;
+    let (daily_limit, lockout_until) = match &transaction.transaction_type {
+        TransactionType::UpdateRestrictions { address, daily_limit, locked_until } => {
+            require_keys_eq!(*address, ctx.accounts.wallet.key(), TokenError::InvalidMultisigTransaction);
+            (*daily_limit, *locked_until)
+        }
+        _ => return err!(TokenError::InvalidMultisigTransaction),
+    };
+    // max_balance is not part of the transaction, so only the daily limit needs checking
+    validate_restrictions(&ctx.accounts.token_config, daily_limit, None)?;
+
+    let restrictions = &mut ctx.accounts.wallet_restrictions;
+    let clock = Clock::get()?;
+
+    // Each approval applies once, and never over a newer one
+    if let Some(last) = restrictions.last_multisig_tx {
+        require!(transaction.transaction_id > last, TokenError::MultisigTransactionAlreadyApplied);
+    }
+
+    restrictions.token_config = ctx.accounts.token_config.key();
+    restrictions.wallet = ctx.accounts.wallet.key();
+    restrictions.daily_transfer_limit = daily_limit;
+    restrictions.lockout_until = lockout_until;
+    restrictions.lockout_notified = false;
+    restrictions.last_multisig_tx = Some(transaction.transaction_id);
+
+    // Initialize tracking if new
+    if restrictions.bump == 0 {
+        restrictions.max_balance = None;
+        restrictions.min_transfer_interval = None;
+        restrictions.transferred_today = 0;
+        restrictions.last_transfer_day = clock.unix_timestamp;
+        restrictions.last_transfer_ts = 0;
+        restrictions.bump = ctx.bumps.wallet_restrictions;
+    }
+
+    emit!(WalletRestrictionsUpdated {
+        token_config: ctx.accounts.token_config.key(),
+        wallet: ctx.accounts.wallet.key(),
+        daily_limit,
+        lockout_until,
+        max_balance: restrictions.max_balance,
+        min_transfer_interval: restrictions.min_transfer_interval,
+        updated_by: ctx.accounts.multisig.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Applied multisig transaction {} to restrictions for wallet: {}",
+        transaction.transaction_id,
+        ctx.accounts.wallet.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetKycLevelLimits<'info> {
+    #[account(
+        mut,
+        constraint = token_config.features.transfer_restrictions_enabled @ TokenError::FeatureDisabled,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    /// Recent admin actions on this token (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, token_config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLog>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_kyc_limits_handler(
+    ctx: Context<SetKycLevelLimits>,
+    limits: [Option<u64>; 4],
+) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.kyc_level_limits = limits;
+
+    let clock = Clock::get()?;
+    record_admin_action(&mut ctx.accounts.audit_log, token_config.key(), ctx.bumps.audit_log, AdminAction::KycLimitsChanged, ctx.accounts.authority.key(), &clock);
+
+    emit!(KycLevelLimitsUpdated {
+        token_config: token_config.key(),
+        limits,
+        updated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Updated KYC level transfer limits for token: {}", token_config.symbol);
+
+    Ok(())
+}
@@ -1,15 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022, Transfer};
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_2022::{self, Token2022, TransferChecked};
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
 
 use crate::state::{AllowlistEntry, AllowlistStatus, WalletRestrictions, ALLOWLIST_SEED, RESTRICTIONS_SEED};
 use crate::errors::TokenError;
-use crate::events::{TokensTransferred, TransferBlocked};
+use crate::events::{TokensTransferred, TransferBlocked, LockoutExpired, WalletApproved};
+use crate::instructions::allowlist::{count_new_entry, require_active_allowlist};
 
 #[derive(Accounts)]
 pub struct TransferTokens<'info> {
     #[account(
+        mut,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
         constraint = !token_config.is_paused @ TokenError::TransfersPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
@@ -23,7 +27,7 @@ pub struct TransferTokens<'info> {
     #[account(
         seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), sender.key().as_ref()],
         bump = sender_allowlist.bump,
-        constraint = sender_allowlist.status == AllowlistStatus::Active @ TokenError::SenderNotApproved,
+        constraint = require_active_allowlist(&sender_allowlist, &token_config.key(), &sender.key()).is_ok() @ TokenError::SenderNotApproved,
     )]
     pub sender_allowlist: Account<'info, AllowlistEntry>,
 
@@ -31,17 +35,19 @@ pub struct TransferTokens<'info> {
     #[account(
         seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), recipient.key().as_ref()],
         bump = recipient_allowlist.bump,
-        constraint = recipient_allowlist.status == AllowlistStatus::Active @ TokenError::RecipientNotApproved,
+        constraint = require_active_allowlist(&recipient_allowlist, &token_config.key(), &recipient.key()).is_ok() @ TokenError::RecipientNotApproved,
     )]
     pub recipient_allowlist: Account<'info, AllowlistEntry>,
 
-    // Optional sender restrictions
+    // Sender restrictions (created on first use; tracks cooldowns and daily totals)
     #[account(
-        mut,
+        init_if_needed,
+        payer = sender,
+        space = WalletRestrictions::LEN,
         seeds = [RESTRICTIONS_SEED, token_config.key().as_ref(), sender.key().as_ref()],
-        bump = sender_restrictions.bump,
+        bump
     )]
-    pub sender_restrictions: Option<Account<'info, WalletRestrictions>>,
+    pub sender_restrictions: Account<'info, WalletRestrictions>,
 
     #[account(
         mut,
@@ -53,9 +59,17 @@ pub struct TransferTokens<'info> {
     #[account(
         mut,
         token::mint = mint,
+        token::authority = recipient,
     )]
     pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Fee treasury token account (required when a transfer fee is configured)
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub fee_treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     /// CHECK: Recipient wallet
     pub recipient: UncheckedAccount<'info>,
 
@@ -63,90 +77,485 @@ pub struct TransferTokens<'info> {
     pub sender: Signer<'info>,
 
     pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    execute_transfer(
+        TransferParts {
+            token_config: &mut accounts.token_config,
+            mint: &accounts.mint,
+            sender_allowlist: &accounts.sender_allowlist,
+            recipient_allowlist: &accounts.recipient_allowlist,
+            sender_restrictions: &mut accounts.sender_restrictions,
+            sender_restrictions_bump: ctx.bumps.sender_restrictions,
+            sender_token_account: &accounts.sender_token_account,
+            recipient_token_account: &accounts.recipient_token_account,
+            fee_treasury_token_account: accounts.fee_treasury_token_account.as_ref(),
+            recipient: &accounts.recipient,
+            sender: &accounts.sender,
+            token_program: &accounts.token_program,
+        },
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct TransferWithOnboard<'info> {
+    #[account(
+        mut,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    /// Token admin multisig - sender must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&sender.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Box<Account<'info, MultisigConfig>>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Sender allowlist check
+    #[account(
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), sender.key().as_ref()],
+        bump = sender_allowlist.bump,
+        constraint = require_active_allowlist(&sender_allowlist, &token_config.key(), &sender.key()).is_ok() @ TokenError::SenderNotApproved,
+    )]
+    pub sender_allowlist: Box<Account<'info, AllowlistEntry>>,
+
+    /// Created at the given KYC level if the recipient is not yet on the allowlist
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = AllowlistEntry::LEN,
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_allowlist: Box<Account<'info, AllowlistEntry>>,
+
+    // Sender restrictions (created on first use; tracks cooldowns and daily totals)
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = WalletRestrictions::LEN,
+        seeds = [RESTRICTIONS_SEED, token_config.key().as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub sender_restrictions: Box<Account<'info, WalletRestrictions>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sender,
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Fee treasury token account (required when a transfer fee is configured)
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub fee_treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Recipient wallet
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Onboard and fund a wallet in one instruction. A multisig member sending from
+/// their own approved wallet adds the recipient to the allowlist (if absent) at
+/// `kyc_level`, then transfers under the same rules as `transfer_tokens`.
+pub fn onboard_handler(ctx: Context<TransferWithOnboard>, amount: u64, kyc_level: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = ctx.accounts;
+
+    if accounts.recipient_allowlist.wallet == Pubkey::default() {
+        require!((kyc_level as usize) < TokenConfig::KYC_LEVELS, TokenError::InvalidKycLevel);
+        count_new_entry(&mut accounts.token_config)?;
+
+        let entry = &mut accounts.recipient_allowlist;
+        entry.token_config = accounts.token_config.key();
+        entry.wallet = accounts.recipient.key();
+        entry.approved_at = clock.unix_timestamp;
+        entry.approved_by = accounts.sender.key();
+        entry.status = AllowlistStatus::Active;
+        entry.kyc_level = kyc_level;
+        entry.dividend_opt_out = false;
+        entry.fee_exempt = false;
+        entry.bump = ctx.bumps.recipient_allowlist;
+
+        emit!(WalletApproved {
+            token_config: accounts.token_config.key(),
+            wallet: accounts.recipient.key(),
+            kyc_level,
+            approved_by: accounts.sender.key(),
+            slot: clock.slot,
+        });
+    } else {
+        // An existing entry is never overwritten; it must already be active
+        require!(
+            require_active_allowlist(&accounts.recipient_allowlist, &accounts.token_config.key(), &accounts.recipient.key()).is_ok(),
+            TokenError::RecipientNotApproved
+        );
+    }
+
+    execute_transfer(
+        TransferParts {
+            token_config: &mut accounts.token_config,
+            mint: &accounts.mint,
+            sender_allowlist: &accounts.sender_allowlist,
+            recipient_allowlist: &accounts.recipient_allowlist,
+            sender_restrictions: &mut accounts.sender_restrictions,
+            sender_restrictions_bump: ctx.bumps.sender_restrictions,
+            sender_token_account: &accounts.sender_token_account,
+            recipient_token_account: &accounts.recipient_token_account,
+            fee_treasury_token_account: accounts.fee_treasury_token_account.as_deref(),
+            recipient: &accounts.recipient,
+            sender: &accounts.sender,
+            token_program: &accounts.token_program,
+        },
+        amount,
+    )
+}
+
+/// Accounts shared by `transfer_tokens` and `transfer_with_onboard`
+struct TransferParts<'a, 'info> {
+    token_config: &'a mut Account<'info, TokenConfig>,
+    mint: &'a InterfaceAccount<'info, Mint>,
+    sender_allowlist: &'a AllowlistEntry,
+    recipient_allowlist: &'a AllowlistEntry,
+    sender_restrictions: &'a mut Account<'info, WalletRestrictions>,
+    sender_restrictions_bump: u8,
+    sender_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    recipient_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    fee_treasury_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    recipient: &'a UncheckedAccount<'info>,
+    sender: &'a Signer<'info>,
+    token_program: &'a Program<'info, Token2022>,
+}
+
+fn execute_transfer(mut p: TransferParts, amount: u64) -> Result<()> {
     require!(amount > 0, TokenError::InvalidAmount);
+    // Self-transfers move no value but would still count against daily limits and cooldowns
+    require_keys_neq!(p.sender.key(), p.recipient.key(), TokenError::InvalidTransfer);
+    require_keys_neq!(
+        p.sender_token_account.key(),
+        p.recipient_token_account.key(),
+        TokenError::InvalidTransfer
+    );
 
     let clock = Clock::get()?;
 
-    // Check restrictions if present
-    if let Some(ref mut restrictions) = ctx.accounts.sender_restrictions {
-        // Check lockout period
-        if let Some(lockout_until) = restrictions.lockout_until {
-            if clock.unix_timestamp < lockout_until {
-                emit!(TransferBlocked {
-                    token_config: ctx.accounts.token_config.key(),
-                    from: ctx.accounts.sender.key(),
-                    to: ctx.accounts.recipient.key(),
-                    amount,
-                    reason: "Wallet is in lockout period".to_string(),
-                    slot: clock.slot,
-                });
-                return Err(TokenError::InLockoutPeriod.into());
-            }
+    // Each restriction falls back to the token-wide default where the wallet has none
+    let token_config = &*p.token_config;
+    let wallet_lockout = p.sender_restrictions.lockout_until;
+    let lockout = wallet_lockout
+        .map(|until| (until, "Wallet is in lockout period"))
+        .or(token_config.default_lockout_until.map(|until| (until, "Token-wide lockout period")));
+    if let Some((lockout_until, reason)) = lockout {
+        if clock.unix_timestamp < lockout_until {
+            emit!(TransferBlocked {
+                token_config: token_config.key(),
+                from: p.sender.key(),
+                to: p.recipient.key(),
+                amount,
+                reason: reason.to_string(),
+                slot: clock.slot,
+            });
+            return Err(TokenError::InLockoutPeriod.into());
         }
+    }
 
-        // Check daily limit
-        if let Some(daily_limit) = restrictions.daily_transfer_limit {
-            // Reset if new day
-            let current_day = clock.unix_timestamp / 86400;
-            let last_day = restrictions.last_transfer_day / 86400;
+    let wallet_daily_limit = p.sender_restrictions.daily_transfer_limit;
+    let daily_limit = wallet_daily_limit.or(token_config.default_daily_limit);
 
-            if current_day > last_day {
-                restrictions.transferred_today = 0;
-                restrictions.last_transfer_day = clock.unix_timestamp;
-            }
+    // The sender's KYC tier cap is a daily allowance too; the tighter of the two applies
+    let kyc_level = p.sender_allowlist.kyc_level as usize;
+    let tier_limit = token_config.kyc_level_limits
+        .get(kyc_level)
+        .copied()
+        .flatten();
+    let effective_limit = match (tier_limit, daily_limit) {
+        (Some(tier), Some(daily)) => Some(tier.min(daily)),
+        (tier, daily) => tier.or(daily),
+    };
 
-            let new_total = restrictions.transferred_today
-                .checked_add(amount)
-                .ok_or(TokenError::MathOverflow)?;
+    // Per-wallet restrictions and running totals
+    let restrictions = p.sender_restrictions;
+    // Created by this transfer: no wallet-specific limits, tracking only
+    if restrictions.wallet == Pubkey::default() {
+        restrictions.token_config = token_config.key();
+        restrictions.wallet = p.sender.key();
+        restrictions.transferred_today = 0;
+        restrictions.last_transfer_day = clock.unix_timestamp;
+        restrictions.last_transfer_ts = 0;
+        restrictions.bump = p.sender_restrictions_bump;
+    }
 
-            if new_total > daily_limit {
-                emit!(TransferBlocked {
-                    token_config: ctx.accounts.token_config.key(),
-                    from: ctx.accounts.sender.key(),
-                    to: ctx.accounts.recipient.key(),
-                    amount,
-                    reason: "Transfer exceeds daily limit".to_string(),
-                    slot: clock.slot,
-                });
-                return Err(TokenError::DailyLimitExceeded.into());
-            }
+    // Check transfer cooldown
+    if let Some(min_interval) = restrictions.min_transfer_interval {
+        let elapsed = clock.unix_timestamp.saturating_sub(restrictions.last_transfer_ts);
+        if elapsed < min_interval as i64 {
+            emit!(TransferBlocked {
+                token_config: token_config.key(),
+                from: p.sender.key(),
+                to: p.recipient.key(),
+                amount,
+                reason: "Transfer cooldown is still active".to_string(),
+                slot: clock.slot,
+            });
+            return Err(TokenError::TransferCooldownActive.into());
+        }
+    }
+
+    // Check the running daily total against the effective limit
+    if let Some(effective_limit) = effective_limit {
+        // Reset if new day
+        let current_day = clock.unix_timestamp / 86400;
+        let last_day = restrictions.last_transfer_day / 86400;
+
+        if current_day > last_day {
+            restrictions.transferred_today = 0;
+            restrictions.last_transfer_day = clock.unix_timestamp;
+        }
+
+        let new_total = restrictions.transferred_today
+            .checked_add(amount)
+            .ok_or(TokenError::MathOverflow)?;
 
-            restrictions.transferred_today = new_total;
+        if new_total > effective_limit {
+            let (reason, error) = if daily_limit.is_some_and(|limit| new_total > limit) {
+                let reason = if wallet_daily_limit.is_some() {
+                    "Transfer exceeds daily limit"
+                } else {
+                    "Transfer exceeds token-wide daily limit"
+                };
+                (reason, TokenError::DailyLimitExceeded)
+            } else {
+                ("Transfer exceeds KYC level daily limit", TokenError::KycTierLimitExceeded)
+            };
+            emit!(TransferBlocked {
+                token_config: token_config.key(),
+                from: p.sender.key(),
+                to: p.recipient.key(),
+                amount,
+                reason: reason.to_string(),
+                slot: clock.slot,
+            });
+            return Err(error.into());
+        }
+
+        restrictions.transferred_today = new_total;
+    }
+
+    restrictions.last_transfer_ts = clock.unix_timestamp;
+
+    // First successful transfer after a lockout ends
+    if let Some(lockout_until) = restrictions.lockout_until {
+        if !restrictions.lockout_notified {
+            restrictions.lockout_notified = true;
+            emit!(LockoutExpired {
+                token_config: p.token_config.key(),
+                wallet: p.sender.key(),
+                lockout_until,
+                slot: clock.slot,
+            });
         }
     }
 
     // Check recipient max balance if restrictions exist
     // (Would need recipient_restrictions account for this)
 
+    // Split off the transfer fee, if configured and neither side is exempt
+    let fee_exempt = p.sender_allowlist.fee_exempt || p.recipient_allowlist.fee_exempt;
+    let fee = match p.token_config.transfer_fee_bps {
+        Some(bps) if !fee_exempt => (amount as u128 * bps as u128 / 10_000) as u64,
+        _ => 0,
+    };
+    let net_amount = amount - fee;
+    // A transfer the fee consumes entirely delivers nothing to the recipient
+    if net_amount == 0 {
+        emit!(TransferBlocked {
+            token_config: p.token_config.key(),
+            from: p.sender.key(),
+            to: p.recipient.key(),
+            amount,
+            reason: "Transfer delivers nothing after fees".to_string(),
+            slot: clock.slot,
+        });
+        return Err(TokenError::InvalidTransfer.into());
+    }
+
+    let is_new_holder = p.recipient_token_account.amount == 0;
+    let sender_exits = p.sender_token_account.amount == amount;
+    let decimals = p.mint.decimals;
+
+    if fee > 0 {
+        let fee_treasury = p.fee_treasury_token_account
+            .ok_or(TokenError::InvalidFeeTreasury)?;
+        require_keys_eq!(fee_treasury.key(), p.token_config.fee_treasury, TokenError::InvalidFeeTreasury);
+
+        token_2022::transfer_checked(
+            CpiContext::new(
+                p.token_program.to_account_info(),
+                TransferChecked {
+                    from: p.sender_token_account.to_account_info(),
+                    mint: p.mint.to_account_info(),
+                    to: fee_treasury.to_account_info(),
+                    authority: p.sender.to_account_info(),
+                },
+            ),
+            fee,
+            decimals,
+        )?;
+    }
+
     // Execute transfer
-    token_2022::transfer(
+    token_2022::transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.sender_token_account.to_account_info(),
-                to: ctx.accounts.recipient_token_account.to_account_info(),
-                authority: ctx.accounts.sender.to_account_info(),
+            p.token_program.to_account_info(),
+            TransferChecked {
+                from: p.sender_token_account.to_account_info(),
+                mint: p.mint.to_account_info(),
+                to: p.recipient_token_account.to_account_info(),
+                authority: p.sender.to_account_info(),
             },
         ),
-        amount,
+        net_amount,
+        decimals,
     )?;
 
+    // Update holder count (self-transfers leave it unchanged)
+    if p.sender_token_account.key() != p.recipient_token_account.key() {
+        let token_config = &mut *p.token_config;
+        if is_new_holder {
+            token_config.holder_count = token_config.holder_count
+                .checked_add(1)
+                .ok_or(TokenError::MathOverflow)?;
+        }
+        if sender_exits {
+            token_config.holder_count = token_config.holder_count
+                .checked_sub(1)
+                .ok_or(TokenError::MathOverflow)?;
+        }
+    }
+
     emit!(TokensTransferred {
-        token_config: ctx.accounts.token_config.key(),
-        from: ctx.accounts.sender.key(),
-        to: ctx.accounts.recipient.key(),
+        token_config: p.token_config.key(),
+        from: p.sender.key(),
+        to: p.recipient.key(),
         amount,
+        fee,
+        fee_exempt,
         slot: clock.slot,
     });
 
-    msg!("Transferred {} tokens from {} to {}",
+    msg!("Transferred {} tokens (fee {}) from {} to {}",
         amount,
-        ctx.accounts.sender.key(),
-        ctx.accounts.recipient.key()
+        fee,
+        p.sender.key(),
+        p.recipient.key()
     );
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct GetTransferAllowance<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), wallet.key().as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    #[account(
+        seeds = [RESTRICTIONS_SEED, token_config.key().as_ref(), wallet.key().as_ref()],
+        bump = wallet_restrictions.bump,
+    )]
+    pub wallet_restrictions: Option<Account<'info, WalletRestrictions>>,
+
+    #[account(
+        token::mint = token_config.mint,
+        token::authority = wallet,
+    )]
+    pub wallet_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Wallet whose allowance is being queried
+    pub wallet: UncheckedAccount<'info>,
+}
+
+/// Read-only: returns the largest amount the wallet could send right now via return data (u64).
+/// Mirrors the checks in `handler`; returns 0 while paused, retired, not allowlisted,
+/// locked out, or in a cooldown. The amount includes any transfer fee.
+pub fn get_allowance_handler(ctx: Context<GetTransferAllowance>) -> Result<()> {
+    let clock = Clock::get()?;
+    let token_config = &ctx.accounts.token_config;
+
+    let blocked = !token_config.is_active
+        || token_config.is_paused
+        || require_active_allowlist(&ctx.accounts.allowlist_entry, &token_config.key(), &ctx.accounts.wallet.key()).is_err();
+
+    let mut allowance = if blocked { 0 } else { ctx.accounts.wallet_token_account.amount };
+
+    // Each restriction falls back to the token-wide default where the wallet has none
+    let restrictions = ctx.accounts.wallet_restrictions.as_ref();
+    let lockout_until = restrictions
+        .and_then(|r| r.lockout_until)
+        .or(token_config.default_lockout_until);
+    if lockout_until.is_some_and(|until| clock.unix_timestamp < until) {
+        allowance = 0;
+    }
+
+    let daily_limit = restrictions
+        .and_then(|r| r.daily_transfer_limit)
+        .or(token_config.default_daily_limit);
+    // The KYC tier cap counts against the same running daily total
+    let kyc_level = ctx.accounts.allowlist_entry.kyc_level as usize;
+    let tier_limit = token_config.kyc_level_limits.get(kyc_level).copied().flatten();
+    let effective_limit = match (tier_limit, daily_limit) {
+        (Some(tier), Some(daily)) => Some(tier.min(daily)),
+        (tier, daily) => tier.or(daily),
+    };
+    if let Some(effective_limit) = effective_limit {
+        // The running total resets at the first transfer of a new day
+        let current_day = clock.unix_timestamp / 86400;
+        let transferred_today = restrictions
+            .filter(|r| current_day <= r.last_transfer_day / 86400)
+            .map_or(0, |r| r.transferred_today);
+        allowance = allowance.min(effective_limit.saturating_sub(transferred_today));
+    }
+
+    if let Some(restrictions) = restrictions {
+        if let Some(min_interval) = restrictions.min_transfer_interval {
+            let elapsed = clock.unix_timestamp.saturating_sub(restrictions.last_transfer_ts);
+            if elapsed < min_interval as i64 {
+                allowance = 0;
+            }
+        }
+    }
+
+    set_return_data(&allowance.to_le_bytes());
+
+    Ok(())
+}
@@ -1,17 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022, Transfer, TransferChecked};
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_2022::{self, CloseAccount, Token2022, Transfer, TransferChecked};
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use chainequity_factory::instructions::create_token::TokenConfig;
+use chainequity_factory::instructions::create_token::{TokenConfig, MultisigConfig};
 
-use crate::state::{VestingSchedule, VestingParams, VestingInterval, TerminationType, VESTING_SEED, VESTING_ESCROW_SEED};
+use crate::state::{VestingSchedule, VestingParams, VestingInterval, TerminationType, BeneficiaryVestingIndex, AllowlistEntry, ALLOWLIST_SEED, VESTING_SEED, VESTING_ESCROW_SEED, VESTING_INDEX_SEED};
 use crate::errors::TokenError;
-use crate::events::{VestingScheduleCreated, VestedTokensReleased, VestingTerminated};
+use crate::events::{VestingScheduleCreated, VestingScheduleToppedUp, VestedTokensReleased, VestingTerminated, VestingCancelled, VestingScheduleClosed, VestingBeneficiaryMigrated};
+use crate::instructions::allowlist::require_active_allowlist;
 
 #[derive(Accounts)]
 #[instruction(params: VestingParams)]
 pub struct CreateVestingSchedule<'info> {
     #[account(
         constraint = token_config.features.vesting_enabled @ TokenError::FeatureDisabled,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -20,8 +24,10 @@ pub struct CreateVestingSchedule<'info> {
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// init_if_needed so a seed collision reaches the handler and fails with
+    /// VestingScheduleExists instead of an opaque account-in-use error
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = VestingSchedule::LEN,
         seeds = [
@@ -34,6 +40,20 @@ pub struct CreateVestingSchedule<'info> {
     )]
     pub vesting_schedule: Account<'info, VestingSchedule>,
 
+    /// Index of the beneficiary's schedules on this token
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BeneficiaryVestingIndex::LEN,
+        seeds = [
+            VESTING_INDEX_SEED,
+            token_config.key().as_ref(),
+            beneficiary.key().as_ref()
+        ],
+        bump
+    )]
+    pub vesting_index: Box<Account<'info, BeneficiaryVestingIndex>>,
+
     /// Escrow account to hold vested tokens
     /// CHECK: PDA that will be the authority for the escrow token account
     #[account(
@@ -49,6 +69,7 @@ pub struct CreateVestingSchedule<'info> {
     #[account(
         mut,
         token::mint = mint,
+        token::authority = escrow_authority,
     )]
     pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -63,6 +84,14 @@ pub struct CreateVestingSchedule<'info> {
     /// CHECK: Beneficiary wallet
     pub beneficiary: UncheckedAccount<'info>,
 
+    /// Beneficiary must be able to legally receive the released shares
+    #[account(
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), beneficiary.key().as_ref()],
+        bump = beneficiary_allowlist.bump,
+        constraint = require_active_allowlist(&beneficiary_allowlist, &token_config.key(), &beneficiary.key()).is_ok() @ TokenError::RecipientNotApproved,
+    )]
+    pub beneficiary_allowlist: Box<Account<'info, AllowlistEntry>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -71,25 +100,54 @@ pub struct CreateVestingSchedule<'info> {
 }
 
 pub fn create_handler(ctx: Context<CreateVestingSchedule>, params: VestingParams) -> Result<()> {
+    // The schedule address is (token, beneficiary, start_time); a second grant with the
+    // same start_time must use a different start_time (e.g. offset by one second)
+    require!(ctx.accounts.vesting_schedule.bump == 0, TokenError::VestingScheduleExists);
     require!(params.total_amount > 0, TokenError::InvalidAmount);
     require!(params.total_duration > 0, TokenError::InvalidVestingDuration);
 
-    // Validate that vesting duration (after cliff) is at least one interval
-    let vesting_duration = params.total_duration.saturating_sub(params.cliff_duration);
+    require!(params.cliff_duration <= params.total_duration, TokenError::InvalidVestingDuration);
+
+    // Validate that vesting duration (after cliff) is at least one interval,
+    // unless this is a cliff-only grant (100% releases at the cliff)
+    let vesting_duration = params.total_duration - params.cliff_duration;
     let interval_seconds = params.interval.to_seconds();
-    require!(vesting_duration >= interval_seconds, TokenError::InvalidVestingDuration);
+    require!(
+        vesting_duration == 0 || vesting_duration >= interval_seconds,
+        TokenError::InvalidVestingDuration
+    );
+
+    // Each interval must release at least one unit, otherwise almost the whole grant
+    // lands in the remainder and most intervals release nothing
+    let total_intervals = if vesting_duration == 0 || interval_seconds == 0 {
+        1
+    } else {
+        vesting_duration / interval_seconds
+    };
+    require!(params.total_amount >= total_intervals, TokenError::VestingAmountTooSmall);
 
     let clock = Clock::get()?;
+
+    // Guard against accidental fully-vested grants from a fat-fingered start_time
+    if !params.backdated {
+        require!(
+            params.start_time >= clock.unix_timestamp.saturating_sub(VestingParams::MAX_BACKDATE_SECONDS),
+            TokenError::InvalidStartTime
+        );
+    }
+
     let schedule = &mut ctx.accounts.vesting_schedule;
 
     schedule.token_config = ctx.accounts.token_config.key();
     schedule.beneficiary = ctx.accounts.beneficiary.key();
+    schedule.seed_beneficiary = ctx.accounts.beneficiary.key();
     schedule.total_amount = params.total_amount;
     schedule.released_amount = 0;
     schedule.start_time = params.start_time;
     schedule.cliff_duration = params.cliff_duration;
     schedule.total_duration = params.total_duration;
     schedule.interval = params.interval.clone();
+    schedule.remainder_policy = params.remainder_policy.clone();
     schedule.intervals_released = 0;
     schedule.revocable = params.revocable;
     schedule.revoked = false;
@@ -98,8 +156,22 @@ pub fn create_handler(ctx: Context<CreateVestingSchedule>, params: VestingParams
     schedule.terminated_by = None;
     schedule.vested_at_termination = None;
     schedule.termination_notes = None;
+    schedule.funder = ctx.accounts.authority.key();
     schedule.bump = ctx.bumps.vesting_schedule;
 
+    // Record the schedule in the beneficiary's index
+    let index = &mut ctx.accounts.vesting_index;
+    if index.bump == 0 {
+        index.token_config = ctx.accounts.token_config.key();
+        index.beneficiary = ctx.accounts.beneficiary.key();
+        index.bump = ctx.bumps.vesting_index;
+    }
+    require!(
+        index.schedules.len() < BeneficiaryVestingIndex::MAX_SCHEDULES,
+        TokenError::VestingIndexFull
+    );
+    index.schedules.push(schedule.key());
+
     // Transfer tokens from authority to escrow
     let decimals = ctx.accounts.mint.decimals;
     token_2022::transfer_checked(
@@ -131,6 +203,7 @@ pub fn create_handler(ctx: Context<CreateVestingSchedule>, params: VestingParams
         interval: params.interval,
         total_intervals,
         amount_per_interval,
+        remainder_policy: schedule.remainder_policy.clone(),
         created_by: ctx.accounts.authority.key(),
         slot: clock.slot,
     });
@@ -145,8 +218,127 @@ pub fn create_handler(ctx: Context<CreateVestingSchedule>, params: VestingParams
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct AddToVesting<'info> {
+    #[account(
+        constraint = token_config.features.vesting_enabled @ TokenError::FeatureDisabled,
+        constraint = token_config.is_active @ TokenError::TokenRetired,
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
+            &vesting_schedule.start_time.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.revoked @ TokenError::AlreadyTerminated,
+        constraint = vesting_schedule.funder == authority.key() @ TokenError::Unauthorized,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// CHECK: PDA authority for escrow token account
+    #[account(
+        seeds = [
+            VESTING_ESCROW_SEED,
+            vesting_schedule.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = escrow_authority,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Funder's token account supplying the additional tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Grow an existing grant on the same terms. The additional amount is spread across
+/// all intervals; the portion belonging to intervals already released is paid out
+/// with the next release.
+pub fn add_handler(ctx: Context<AddToVesting>, additional_amount: u64) -> Result<()> {
+    require!(additional_amount > 0, TokenError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let schedule = &mut ctx.accounts.vesting_schedule;
+
+    // Once every interval is released there is no later release to carry the top-up
+    require!(
+        schedule.intervals_released < schedule.total_intervals(),
+        TokenError::VestingFullyReleased
+    );
+
+    schedule.total_amount = schedule.total_amount
+        .checked_add(additional_amount)
+        .ok_or(TokenError::MathOverflow)?;
+
+    let decimals = ctx.accounts.mint.decimals;
+    token_2022::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        additional_amount,
+        decimals,
+    )?;
+
+    let amount_per_interval = schedule.amount_per_interval();
+
+    emit!(VestingScheduleToppedUp {
+        token_config: ctx.accounts.token_config.key(),
+        schedule: schedule.key(),
+        beneficiary: schedule.beneficiary,
+        additional_amount,
+        new_total_amount: schedule.total_amount,
+        amount_per_interval,
+        added_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Added {} tokens to vesting schedule for {} (new total {}, {} per interval)",
+        additional_amount,
+        schedule.beneficiary,
+        schedule.total_amount,
+        amount_per_interval
+    );
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct ReleaseVestedTokens<'info> {
+    #[account(
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -159,7 +351,7 @@ pub struct ReleaseVestedTokens<'info> {
         seeds = [
             VESTING_SEED,
             token_config.key().as_ref(),
-            beneficiary.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
             &vesting_schedule.start_time.to_le_bytes()
         ],
         bump = vesting_schedule.bump,
@@ -180,6 +372,7 @@ pub struct ReleaseVestedTokens<'info> {
     #[account(
         mut,
         token::mint = mint,
+        token::authority = escrow_authority,
     )]
     pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -205,44 +398,17 @@ pub fn release_handler(ctx: Context<ReleaseVestedTokens>) -> Result<()> {
 
     let schedule = &mut ctx.accounts.vesting_schedule;
 
-    // Calculate new intervals available
-    let new_intervals = calculate_releasable_intervals(schedule, clock.unix_timestamp);
-    require!(new_intervals > 0, TokenError::NoTokensToRelease);
-
-    // Calculate amount to release for these intervals
-    let total_intervals = schedule.total_intervals();
-    let amount_per_interval = schedule.amount_per_interval();
-    let remainder = schedule.remainder();
-
-    // Calculate release amount: intervals * amount_per_interval + any remainder shares
-    let previous_intervals = schedule.intervals_released;
-    let new_total_intervals = previous_intervals + new_intervals;
-
-    // Base release for new intervals
-    let mut release_amount = amount_per_interval * new_intervals;
-
-    // Add remainder shares for final intervals
-    // Remainder is distributed to the last N intervals where N = remainder
-    let remainder_start = total_intervals.saturating_sub(remainder);
-    if new_total_intervals > remainder_start && previous_intervals < total_intervals {
-        // Calculate how many of the new intervals are in the remainder zone
-        let remainder_intervals_before = if previous_intervals > remainder_start {
-            previous_intervals - remainder_start
-        } else {
-            0
-        };
-        let remainder_intervals_now = if new_total_intervals > remainder_start {
-            (new_total_intervals - remainder_start).min(remainder)
-        } else {
-            0
-        };
-        release_amount += remainder_intervals_now.saturating_sub(remainder_intervals_before);
-    }
+    // Distinguish "cliff not reached" from "nothing new since the last release"
+    let cliff_ends = schedule.start_time
+        .checked_add(schedule.cliff_duration as i64)
+        .ok_or(TokenError::MathOverflow)?;
+    require!(clock.unix_timestamp >= cliff_ends, TokenError::CliffNotReached);
 
-    require!(release_amount > 0, TokenError::NoTokensToRelease);
+    let (new_intervals, release_amount) = calculate_release(schedule, clock.unix_timestamp)?;
+    require!(new_intervals > 0 && release_amount > 0, TokenError::NoTokensToRelease);
 
     // Update schedule state
-    schedule.intervals_released = new_total_intervals;
+    schedule.intervals_released += new_intervals;
     schedule.released_amount = schedule.released_amount
         .checked_add(release_amount)
         .ok_or(TokenError::MathOverflow)?;
@@ -291,6 +457,130 @@ pub fn release_handler(ctx: Context<ReleaseVestedTokens>) -> Result<()> {
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct BatchReleaseVestedTokens<'info> {
+    #[account(
+        constraint = !token_config.is_paused @ TokenError::TransfersPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = beneficiary,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    // Remaining accounts: (vesting_schedule, escrow_authority, escrow_token_account) per schedule.
+    // The escrow authority PDA must be present for it to sign the transfer out of escrow.
+}
+
+pub fn batch_release_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchReleaseVestedTokens<'info>>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len() % 3 == 0,
+        TokenError::InvalidRemainingAccounts
+    );
+    require!(
+        remaining.len() / 3 <= VestingSchedule::MAX_BATCH_RELEASE,
+        TokenError::ReleaseBatchTooLarge
+    );
+
+    let clock = Clock::get()?;
+    let token_config_key = ctx.accounts.token_config.key();
+    let beneficiary_key = ctx.accounts.beneficiary.key();
+    let decimals = ctx.accounts.mint.decimals;
+    let mut total_released: u64 = 0;
+
+    for accounts in remaining.chunks(3) {
+        let (schedule_info, escrow_authority, escrow_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        let mut schedule: Account<'info, VestingSchedule> = Account::try_from(schedule_info)?;
+        require!(schedule_info.is_writable, TokenError::InvalidRemainingAccounts);
+        require!(schedule.token_config == token_config_key, TokenError::Unauthorized);
+        require!(schedule.beneficiary == beneficiary_key, TokenError::Unauthorized);
+
+        let (expected_authority, escrow_bump) = Pubkey::find_program_address(
+            &[VESTING_ESCROW_SEED, schedule_info.key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(escrow_authority.key(), expected_authority, TokenError::InvalidRemainingAccounts);
+
+        let escrow_token_account: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(escrow_info)?;
+        require_keys_eq!(escrow_token_account.mint, ctx.accounts.mint.key(), TokenError::InvalidRemainingAccounts);
+        require_keys_eq!(escrow_token_account.owner, escrow_authority.key(), TokenError::InvalidRemainingAccounts);
+
+        let (new_intervals, release_amount) = calculate_release(&schedule, clock.unix_timestamp)?;
+        if new_intervals == 0 || release_amount == 0 {
+            continue;
+        }
+
+        schedule.intervals_released += new_intervals;
+        schedule.released_amount = schedule.released_amount
+            .checked_add(release_amount)
+            .ok_or(TokenError::MathOverflow)?;
+        schedule.exit(ctx.program_id)?;
+
+        let escrow_seeds: &[&[u8]] = &[
+            VESTING_ESCROW_SEED,
+            schedule_info.key.as_ref(),
+            &[escrow_bump],
+        ];
+        let signer_seeds = &[escrow_seeds];
+
+        token_2022::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: escrow_authority.clone(),
+                },
+                signer_seeds,
+            ),
+            release_amount,
+            decimals,
+        )?;
+
+        total_released = total_released
+            .checked_add(release_amount)
+            .ok_or(TokenError::MathOverflow)?;
+
+        emit!(VestedTokensReleased {
+            token_config: token_config_key,
+            schedule: schedule.key(),
+            beneficiary: beneficiary_key,
+            amount_released: release_amount,
+            total_released: schedule.released_amount,
+            intervals_released: new_intervals,
+            total_intervals_released: schedule.intervals_released,
+            slot: clock.slot,
+        });
+    }
+
+    require!(total_released > 0, TokenError::NoTokensToRelease);
+
+    msg!("Batch released {} vested tokens across {} schedules to {}",
+        total_released,
+        remaining.len() / 3,
+        beneficiary_key
+    );
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct TerminateVesting<'info> {
     pub token_config: Account<'info, TokenConfig>,
@@ -305,7 +595,7 @@ pub struct TerminateVesting<'info> {
         seeds = [
             VESTING_SEED,
             token_config.key().as_ref(),
-            vesting_schedule.beneficiary.as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
             &vesting_schedule.start_time.to_le_bytes()
         ],
         bump = vesting_schedule.bump,
@@ -326,6 +616,7 @@ pub struct TerminateVesting<'info> {
     #[account(
         mut,
         token::mint = mint,
+        token::authority = escrow_authority,
     )]
     pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -333,6 +624,7 @@ pub struct TerminateVesting<'info> {
     #[account(
         mut,
         token::mint = mint,
+        constraint = treasury_token_account.owner == token_config.treasury @ TokenError::Unauthorized,
     )]
     pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -360,13 +652,18 @@ pub fn terminate_handler(
     let schedule = &mut ctx.accounts.vesting_schedule;
 
     // Calculate vested at current time
-    let vested_now = calculate_vested_amount(schedule, clock.unix_timestamp);
+    let vested_now = calculate_vested_amount(schedule, clock.unix_timestamp)?;
 
     // Determine final vested based on termination type
     let final_vested = match termination_type {
         TerminationType::Standard => vested_now,
         TerminationType::ForCause => 0,
         TerminationType::Accelerated => schedule.total_amount,
+        TerminationType::PartialAcceleration { additional_vested } => {
+            let unvested = schedule.total_amount.saturating_sub(vested_now);
+            require!(additional_vested <= unvested, TokenError::InvalidAcceleration);
+            vested_now + additional_vested
+        }
     };
 
     // Calculate amounts
@@ -428,36 +725,413 @@ pub fn terminate_handler(
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct CancelVesting<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ TokenError::Unauthorized,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
+            &vesting_schedule.start_time.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.revoked @ TokenError::AlreadyTerminated,
+        close = funder,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_INDEX_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref()
+        ],
+        bump = vesting_index.bump,
+    )]
+    pub vesting_index: Box<Account<'info, BeneficiaryVestingIndex>>,
+
+    /// CHECK: PDA authority for escrow token account
+    #[account(
+        seeds = [
+            VESTING_ESCROW_SEED,
+            vesting_schedule.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = escrow_authority,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury token account to receive the escrowed tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = treasury_token_account.owner == token_config.treasury @ TokenError::Unauthorized,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Original funder of the schedule; receives the reclaimed rent
+    #[account(
+        mut,
+        constraint = funder.key() == vesting_schedule.funder @ TokenError::Unauthorized,
+    )]
+    pub funder: UncheckedAccount<'info>,
+
+    /// Token admin multisig - required unless the authority is the schedule's funder
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+    )]
+    pub multisig: Option<Account<'info, MultisigConfig>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Cancel a mistaken schedule before anything has vested, returning the full
+/// escrow to treasury and closing the schedule with rent refunded to the funder.
+/// Use terminate for real departures.
+pub fn cancel_handler(ctx: Context<CancelVesting>) -> Result<()> {
+    let clock = Clock::get()?;
+    let schedule = &ctx.accounts.vesting_schedule;
+    require!(schedule.revocable, TokenError::NotRevocable);
+
+    // Only the original funder or a token multisig signer may cancel
+    let authority = ctx.accounts.authority.key();
+    let is_admin = ctx.accounts.multisig
+        .as_ref()
+        .is_some_and(|m| m.signers.contains(&authority));
+    require!(authority == schedule.funder || is_admin, TokenError::Unauthorized);
+
+    // Only allowed before the first tokens vest
+    let vesting_begins = schedule.start_time
+        .checked_add(schedule.cliff_duration as i64)
+        .ok_or(TokenError::MathOverflow)?;
+    require!(schedule.released_amount == 0, TokenError::VestingAlreadyStarted);
+    require!(clock.unix_timestamp < vesting_begins, TokenError::VestingAlreadyStarted);
+
+    let vesting_schedule_key = schedule.key();
+    let beneficiary = schedule.beneficiary;
+    let to_return = schedule.total_amount;
+    let escrow_bump = ctx.bumps.escrow_authority;
+
+    // Return the full escrow to treasury
+    let escrow_seeds: &[&[u8]] = &[
+        VESTING_ESCROW_SEED,
+        vesting_schedule_key.as_ref(),
+        &[escrow_bump],
+    ];
+    let signer_seeds = &[escrow_seeds];
+
+    let decimals = ctx.accounts.mint.decimals;
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        to_return,
+        decimals,
+    )?;
+
+    // Drop the schedule from the beneficiary's index
+    ctx.accounts.vesting_index.schedules.retain(|s| *s != vesting_schedule_key);
+
+    emit!(VestingCancelled {
+        token_config: ctx.accounts.token_config.key(),
+        schedule: vesting_schedule_key,
+        beneficiary,
+        returned_to_treasury: to_return,
+        cancelled_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Cancelled vesting schedule {}. Returned {} to treasury", vesting_schedule_key, to_return);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVestingSchedule<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
+            &vesting_schedule.start_time.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_settled() @ TokenError::VestingNotSettled,
+        close = funder,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_INDEX_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref()
+        ],
+        bump = vesting_index.bump,
+    )]
+    pub vesting_index: Box<Account<'info, BeneficiaryVestingIndex>>,
+
+    /// CHECK: PDA authority for escrow token account
+    #[account(
+        seeds = [
+            VESTING_ESCROW_SEED,
+            vesting_schedule.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_config.mint,
+        token::authority = escrow_authority,
+        constraint = escrow_token_account.amount == 0 @ TokenError::EscrowNotEmpty,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Original funder of the schedule; receives the reclaimed rent
+    #[account(
+        mut,
+        constraint = funder.key() == vesting_schedule.funder @ TokenError::Unauthorized,
+    )]
+    pub funder: UncheckedAccount<'info>,
+
+    pub closer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Close a settled schedule and its empty escrow, refunding rent to the funder
+pub fn close_handler(ctx: Context<CloseVestingSchedule>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vesting_schedule_key = ctx.accounts.vesting_schedule.key();
+    let beneficiary = ctx.accounts.vesting_schedule.beneficiary;
+    let escrow_bump = ctx.bumps.escrow_authority;
+
+    let escrow_seeds: &[&[u8]] = &[
+        VESTING_ESCROW_SEED,
+        vesting_schedule_key.as_ref(),
+        &[escrow_bump],
+    ];
+    let signer_seeds = &[escrow_seeds];
+
+    token_2022::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.funder.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    // Drop the schedule from the beneficiary's index
+    ctx.accounts.vesting_index.schedules.retain(|s| *s != vesting_schedule_key);
+
+    emit!(VestingScheduleClosed {
+        token_config: ctx.accounts.token_config.key(),
+        schedule: vesting_schedule_key,
+        beneficiary,
+        funder: ctx.accounts.funder.key(),
+        closed_by: ctx.accounts.closer.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Closed settled vesting schedule {}", vesting_schedule_key);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(new_beneficiary: Pubkey)]
+pub struct MigrateVestingBeneficiary<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Token admin multisig - authority must be one of its signers
+    #[account(
+        constraint = multisig.key() == token_config.authority @ TokenError::Unauthorized,
+        constraint = multisig.signers.contains(&authority.key()) @ TokenError::Unauthorized,
+    )]
+    pub multisig: Account<'info, MultisigConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
+            &vesting_schedule.start_time.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Current beneficiary's index; the schedule is removed from it
+    #[account(
+        mut,
+        seeds = [
+            VESTING_INDEX_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref()
+        ],
+        bump = old_vesting_index.bump,
+    )]
+    pub old_vesting_index: Box<Account<'info, BeneficiaryVestingIndex>>,
+
+    /// New beneficiary's index; the schedule is added to it
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BeneficiaryVestingIndex::LEN,
+        seeds = [
+            VESTING_INDEX_SEED,
+            token_config.key().as_ref(),
+            new_beneficiary.as_ref()
+        ],
+        bump
+    )]
+    pub new_vesting_index: Box<Account<'info, BeneficiaryVestingIndex>>,
+
+    /// New beneficiary must be on the allowlist, as at schedule creation
+    #[account(
+        seeds = [ALLOWLIST_SEED, token_config.key().as_ref(), new_beneficiary.as_ref()],
+        bump = new_beneficiary_allowlist.bump,
+        constraint = require_active_allowlist(&new_beneficiary_allowlist, &token_config.key(), &new_beneficiary).is_ok() @ TokenError::RecipientNotApproved,
+    )]
+    pub new_beneficiary_allowlist: Box<Account<'info, AllowlistEntry>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a schedule to a new beneficiary wallet (custody change or key rotation).
+/// The schedule keeps its address because its PDA is seeded by `seed_beneficiary`;
+/// only `beneficiary`, which gates release, changes.
+pub fn migrate_beneficiary_handler(ctx: Context<MigrateVestingBeneficiary>, new_beneficiary: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let schedule = &mut ctx.accounts.vesting_schedule;
+    let schedule_key = schedule.key();
+    let old_beneficiary = schedule.beneficiary;
+    require_keys_neq!(old_beneficiary, new_beneficiary, TokenError::InvalidBeneficiary);
+
+    schedule.beneficiary = new_beneficiary;
+
+    ctx.accounts.old_vesting_index.schedules.retain(|s| *s != schedule_key);
+
+    let index = &mut ctx.accounts.new_vesting_index;
+    if index.bump == 0 {
+        index.token_config = ctx.accounts.token_config.key();
+        index.beneficiary = new_beneficiary;
+        index.bump = ctx.bumps.new_vesting_index;
+    }
+    require!(
+        index.schedules.len() < BeneficiaryVestingIndex::MAX_SCHEDULES,
+        TokenError::VestingIndexFull
+    );
+    index.schedules.push(schedule_key);
+
+    emit!(VestingBeneficiaryMigrated {
+        token_config: ctx.accounts.token_config.key(),
+        schedule: schedule_key,
+        old_beneficiary,
+        new_beneficiary,
+        migrated_by: ctx.accounts.authority.key(),
+        slot: clock.slot,
+    });
+
+    msg!("Migrated vesting schedule {} from {} to {}", schedule_key, old_beneficiary, new_beneficiary);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetVestedAmount<'info> {
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [
+            VESTING_SEED,
+            token_config.key().as_ref(),
+            vesting_schedule.seed_beneficiary.as_ref(),
+            &vesting_schedule.start_time.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Read-only: returns the schedule's vested amount at the current time via return data
+pub fn get_vested_handler(ctx: Context<GetVestedAmount>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vested = calculate_vested_amount(&ctx.accounts.vesting_schedule, clock.unix_timestamp)?;
+
+    set_return_data(&vested.to_le_bytes());
+
+    Ok(())
+}
+
 /// Calculate vested amount at a given timestamp using discrete intervals
 ///
 /// All vesting uses discrete intervals (minute/hour/day/month).
 /// Each interval releases the same amount: total_amount / total_intervals.
-/// Any remainder is distributed to the final intervals.
-pub fn calculate_vested_amount(schedule: &VestingSchedule, current_time: i64) -> u64 {
+/// Any remainder is distributed according to the schedule's remainder_policy.
+pub fn calculate_vested_amount(schedule: &VestingSchedule, current_time: i64) -> Result<u64> {
     // If terminated, use the frozen vested amount
     if let Some(vested_at_term) = schedule.vested_at_termination {
-        return vested_at_term;
+        return Ok(vested_at_term);
     }
 
     // If revoked entirely, nothing further vests
     if schedule.revoked {
-        return schedule.released_amount;
+        return Ok(schedule.released_amount);
     }
 
     let elapsed = current_time - schedule.start_time;
 
     if elapsed < 0 {
-        return 0;
+        return Ok(0);
     }
 
     // If past total duration, return full amount
     if elapsed >= schedule.total_duration as i64 {
-        return schedule.total_amount;
+        return Ok(schedule.total_amount);
     }
 
     // During cliff period, nothing vests
     if elapsed < schedule.cliff_duration as i64 {
-        return 0;
+        return Ok(0);
     }
 
     // Calculate intervals elapsed after cliff
@@ -468,24 +1142,48 @@ pub fn calculate_vested_amount(schedule: &VestingSchedule, current_time: i64) ->
     // Get interval calculations
     let total_intervals = schedule.total_intervals();
     let amount_per_interval = schedule.amount_per_interval();
-    let remainder = schedule.remainder();
 
     if total_intervals == 0 {
-        return schedule.total_amount;
+        return Ok(schedule.total_amount);
     }
 
     // Base vested amount
-    let mut vested = amount_per_interval * intervals_elapsed;
+    let mut vested = checked_mul_u128(amount_per_interval, intervals_elapsed)?;
 
-    // Distribute remainder to final intervals
-    // If remainder is N, the last N intervals each get +1
-    if intervals_elapsed > (total_intervals - remainder) {
-        let extra_intervals = intervals_elapsed - (total_intervals - remainder);
-        vested += extra_intervals;
-    }
+    // Distribute remainder per the schedule's remainder policy
+    vested = vested
+        .checked_add(schedule.remainder_through(intervals_elapsed))
+        .ok_or(TokenError::MathOverflow)?;
 
     // Cap at total amount
-    vested.min(schedule.total_amount)
+    Ok(vested.min(schedule.total_amount))
+}
+
+/// Calculate (new intervals, amount) releasable now, including any remainder
+/// shares that fall in the newly released intervals.
+pub fn calculate_release(schedule: &VestingSchedule, current_time: i64) -> Result<(u64, u64)> {
+    let new_intervals = calculate_releasable_intervals(schedule, current_time);
+    if new_intervals == 0 {
+        return Ok((0, 0));
+    }
+
+    let amount_per_interval = schedule.amount_per_interval();
+    let new_total_intervals = schedule.intervals_released + new_intervals;
+
+    // Everything vested through the new interval count (remainder per policy), less what
+    // has already gone out. Measuring against released_amount means a top-up's share of
+    // earlier intervals is caught up on the next release.
+    let vested_through = checked_mul_u128(amount_per_interval, new_total_intervals)?
+        .checked_add(schedule.remainder_through(new_total_intervals))
+        .ok_or(TokenError::MathOverflow)?;
+    let release_amount = vested_through.saturating_sub(schedule.released_amount);
+
+    Ok((new_intervals, release_amount))
+}
+
+/// Multiply in u128 and convert back, erroring instead of wrapping on overflow
+fn checked_mul_u128(a: u64, b: u64) -> Result<u64> {
+    u64::try_from(a as u128 * b as u128).map_err(|_| error!(TokenError::MathOverflow))
 }
 
 /// Calculate how many NEW intervals are available to release
@@ -509,3 +1207,102 @@ pub fn calculate_releasable_intervals(schedule: &VestingSchedule, current_time:
     // Return new intervals (not yet released)
     intervals_elapsed.saturating_sub(schedule.intervals_released)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::RemainderPolicy;
+
+    const MINUTE: u64 = 60;
+
+    /// Minute-interval schedule starting at t=0
+    fn schedule(total_amount: u64, cliff_duration: u64, intervals: u64, remainder_policy: RemainderPolicy) -> VestingSchedule {
+        VestingSchedule {
+            token_config: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            seed_beneficiary: Pubkey::default(),
+            total_amount,
+            released_amount: 0,
+            start_time: 0,
+            cliff_duration,
+            total_duration: cliff_duration + intervals * MINUTE,
+            interval: VestingInterval::Minute,
+            remainder_policy,
+            intervals_released: 0,
+            revocable: true,
+            revoked: false,
+            termination_type: None,
+            terminated_at: None,
+            terminated_by: None,
+            vested_at_termination: None,
+            termination_notes: None,
+            funder: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn nothing_releases_before_the_first_interval_after_the_cliff() {
+        let s = schedule(1_000, 3_600, 10, RemainderPolicy::BackLoaded);
+        assert_eq!(calculate_release(&s, 3_599).unwrap(), (0, 0));
+        assert_eq!(calculate_release(&s, 3_600).unwrap(), (0, 0));
+        assert_eq!(calculate_release(&s, 3_600 + 3 * 60 + 59).unwrap(), (3, 300));
+    }
+
+    #[test]
+    fn cliff_only_schedule_releases_everything_at_the_cliff() {
+        let s = schedule(1_000, 3_600, 0, RemainderPolicy::BackLoaded);
+        assert!(s.is_cliff_only());
+        assert_eq!(calculate_release(&s, 3_599).unwrap(), (0, 0));
+        assert_eq!(calculate_release(&s, 3_600).unwrap(), (1, 1_000));
+    }
+
+    #[test]
+    fn final_interval_releases_the_rest_of_the_grant() {
+        let mut s = schedule(1_003, 0, 10, RemainderPolicy::FinalInterval);
+        s.intervals_released = 9;
+        s.released_amount = 900;
+        assert_eq!(calculate_release(&s, 600).unwrap(), (1, 103));
+
+        let mut s = schedule(1_003, 0, 10, RemainderPolicy::BackLoaded);
+        s.intervals_released = 9;
+        s.released_amount = 902;
+        assert_eq!(calculate_release(&s, 600).unwrap(), (1, 101));
+    }
+
+    #[test]
+    fn remainder_follows_the_schedule_policy() {
+        // 13 over 10 intervals: 1 per interval plus a remainder of 3
+        let front = schedule(13, 0, 10, RemainderPolicy::FrontLoaded);
+        let back = schedule(13, 0, 10, RemainderPolicy::BackLoaded);
+        let last = schedule(13, 0, 10, RemainderPolicy::FinalInterval);
+
+        assert_eq!(calculate_release(&front, 180).unwrap(), (3, 6));
+        assert_eq!(calculate_release(&back, 180).unwrap(), (3, 3));
+        assert_eq!(calculate_release(&last, 180).unwrap(), (3, 3));
+
+        assert_eq!(calculate_release(&back, 540).unwrap(), (9, 11));
+        assert_eq!(calculate_release(&last, 540).unwrap(), (9, 9));
+
+        for s in [&front, &back, &last] {
+            assert_eq!(calculate_release(s, 600).unwrap(), (10, 13));
+        }
+    }
+
+    #[test]
+    fn large_minute_schedule_over_a_year_does_not_overflow() {
+        let intervals = 365 * 24 * 60;
+        let s = schedule(u64::MAX, 0, intervals, RemainderPolicy::BackLoaded);
+
+        let half = intervals / 2;
+        let expected = s.amount_per_interval() as u128 * half as u128
+            + s.remainder_through(half) as u128;
+        let (released_intervals, amount) = calculate_release(&s, (half * MINUTE) as i64).unwrap();
+        assert_eq!(released_intervals, half);
+        assert_eq!(amount as u128, expected);
+
+        let end = (intervals * MINUTE) as i64;
+        assert_eq!(calculate_release(&s, end).unwrap(), (intervals, u64::MAX));
+        assert_eq!(calculate_vested_amount(&s, end).unwrap(), u64::MAX);
+    }
+}
@@ -7,6 +7,7 @@ pub mod events;
 
 use instructions::*;
 use state::*;
+use chainequity_factory::state::TokenFeatures;
 
 declare_id!("5H3QcvZsViboQzqnv2vLjqCNyCgQ4sx3UXmYgDihTmLV");
 
@@ -18,9 +19,19 @@ pub mod chainequity_token {
     // ALLOWLIST MANAGEMENT
     // =========================================================================
 
-    /// Add a wallet to the allowlist
-    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>) -> Result<()> {
-        instructions::allowlist::add_handler(ctx)
+    /// Add a wallet to the allowlist at the given KYC level (0-3)
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, kyc_level: u8) -> Result<()> {
+        instructions::allowlist::add_handler(ctx, kyc_level)
+    }
+
+    /// Submit a wallet for allowlist review (created as Pending)
+    pub fn add_to_allowlist_pending(ctx: Context<AddToAllowlist>, kyc_level: u8) -> Result<()> {
+        instructions::allowlist::add_pending_handler(ctx, kyc_level)
+    }
+
+    /// Approve a Pending allowlist entry, making it Active (kyc_level overrides the submitted level)
+    pub fn approve_allowlist_entry(ctx: Context<ApproveAllowlistEntry>, kyc_level: Option<u8>) -> Result<()> {
+        instructions::allowlist::approve_handler(ctx, kyc_level)
     }
 
     /// Remove a wallet from the allowlist
@@ -28,6 +39,21 @@ pub mod chainequity_token {
         instructions::allowlist::remove_handler(ctx)
     }
 
+    /// Move a wallet's allowlist approval to a replacement wallet (key rotation)
+    pub fn migrate_allowlist(ctx: Context<MigrateAllowlist>) -> Result<()> {
+        instructions::allowlist::migrate_handler(ctx)
+    }
+
+    /// Exclude a wallet from (or re-include it in) dividend distributions
+    pub fn set_dividend_opt_out(ctx: Context<SetDividendOptOut>, opt_out: bool) -> Result<()> {
+        instructions::allowlist::set_dividend_opt_out_handler(ctx, opt_out)
+    }
+
+    /// Exempt a wallet from the transfer fee (applies when it is sender or recipient)
+    pub fn set_fee_exempt(ctx: Context<SetFeeExempt>, exempt: bool) -> Result<()> {
+        instructions::allowlist::set_fee_exempt_handler(ctx, exempt)
+    }
+
     /// Update allowlist status
     pub fn update_allowlist_status(
         ctx: Context<UpdateAllowlistStatus>,
@@ -48,6 +74,14 @@ pub mod chainequity_token {
         instructions::mint::handler(ctx, amount)
     }
 
+    /// Mint to several approved wallets in one transaction (amounts match remaining account pairs)
+    pub fn mint_tokens_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTokensBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::mint::batch_handler(ctx, amounts)
+    }
+
     /// Transfer tokens between approved wallets
     pub fn transfer_tokens(
         ctx: Context<TransferTokens>,
@@ -56,6 +90,20 @@ pub mod chainequity_token {
         instructions::transfer::handler(ctx, amount)
     }
 
+    /// Transfer to a wallet, adding it to the allowlist at `kyc_level` if absent (multisig member only)
+    pub fn transfer_with_onboard(
+        ctx: Context<TransferWithOnboard>,
+        amount: u64,
+        kyc_level: u8,
+    ) -> Result<()> {
+        instructions::transfer::onboard_handler(ctx, amount, kyc_level)
+    }
+
+    /// Get how much a wallet can transfer right now (returned via return data as u64)
+    pub fn get_transfer_allowance(ctx: Context<GetTransferAllowance>) -> Result<()> {
+        instructions::transfer::get_allowance_handler(ctx)
+    }
+
     // =========================================================================
     // VESTING
     // =========================================================================
@@ -68,11 +116,46 @@ pub mod chainequity_token {
         instructions::vesting::create_handler(ctx, params)
     }
 
+    /// Add tokens to an existing schedule on the same terms (funder only)
+    pub fn add_to_vesting(ctx: Context<AddToVesting>, additional_amount: u64) -> Result<()> {
+        instructions::vesting::add_handler(ctx, additional_amount)
+    }
+
     /// Release vested tokens
     pub fn release_vested_tokens(ctx: Context<ReleaseVestedTokens>) -> Result<()> {
         instructions::vesting::release_handler(ctx)
     }
 
+    /// Release vested tokens from several of the caller's schedules in one transaction
+    pub fn batch_release<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchReleaseVestedTokens<'info>>,
+    ) -> Result<()> {
+        instructions::vesting::batch_release_handler(ctx)
+    }
+
+    /// Close a fully released (or terminated and settled) schedule and its empty escrow
+    pub fn close_vesting_schedule(ctx: Context<CloseVestingSchedule>) -> Result<()> {
+        instructions::vesting::close_handler(ctx)
+    }
+
+    /// Move a vesting schedule to a new beneficiary wallet (multisig only)
+    pub fn migrate_vesting_beneficiary(
+        ctx: Context<MigrateVestingBeneficiary>,
+        new_beneficiary: Pubkey,
+    ) -> Result<()> {
+        instructions::vesting::migrate_beneficiary_handler(ctx, new_beneficiary)
+    }
+
+    /// Cancel a schedule created in error before anything vests (full refund, closes account)
+    pub fn cancel_vesting(ctx: Context<CancelVesting>) -> Result<()> {
+        instructions::vesting::cancel_handler(ctx)
+    }
+
+    /// Get the vested amount of a schedule (returned via return data as u64)
+    pub fn get_vested_amount(ctx: Context<GetVestedAmount>) -> Result<()> {
+        instructions::vesting::get_vested_handler(ctx)
+    }
+
     /// Terminate a vesting schedule
     pub fn terminate_vesting(
         ctx: Context<TerminateVesting>,
@@ -86,27 +169,40 @@ pub mod chainequity_token {
     // RESTRICTIONS
     // =========================================================================
 
-    /// Set wallet restrictions (daily limit, lockout)
+    /// Set wallet restrictions (daily limit, lockout, transfer cooldown)
     pub fn set_wallet_restrictions(
         ctx: Context<SetWalletRestrictions>,
         daily_limit: Option<u64>,
         lockout_until: Option<i64>,
         max_balance: Option<u64>,
+        min_transfer_interval: Option<u64>,
+    ) -> Result<()> {
+        instructions::restrictions::set_handler(ctx, daily_limit, lockout_until, max_balance, min_transfer_interval)
+    }
+
+    /// Apply wallet restrictions from an executed factory multisig UpdateRestrictions transaction
+    pub fn set_wallet_restrictions_multisig(ctx: Context<SetWalletRestrictionsMultisig>) -> Result<()> {
+        instructions::restrictions::set_multisig_handler(ctx)
+    }
+
+    /// Set per-KYC-level daily transfer caps for the token
+    pub fn set_kyc_level_limits(
+        ctx: Context<SetKycLevelLimits>,
+        limits: [Option<u64>; 4],
     ) -> Result<()> {
-        instructions::restrictions::set_handler(ctx, daily_limit, lockout_until, max_balance)
+        instructions::restrictions::set_kyc_limits_handler(ctx, limits)
     }
 
     // =========================================================================
     // CORPORATE ACTIONS
     // =========================================================================
 
-    /// Execute a stock split
-    pub fn execute_split_batch(
-        ctx: Context<ExecuteSplitBatch>,
+    /// Execute a stock split batch (holder token accounts in ascending key order)
+    pub fn execute_split_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSplitBatch<'info>>,
         split_ratio: u8,
-        batch_index: u32,
     ) -> Result<()> {
-        instructions::corporate_actions::split_batch_handler(ctx, split_ratio, batch_index)
+        instructions::corporate_actions::split_batch_handler(ctx, split_ratio)
     }
 
     /// Finalize a stock split
@@ -129,33 +225,135 @@ pub mod chainequity_token {
     // DIVIDENDS
     // =========================================================================
 
-    /// Create a dividend round
+    /// Create a dividend round. snapshot_slot sets a past record slot (None = current slot).
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dividend_round(
         ctx: Context<CreateDividendRound>,
         round_id: u64,
+        kind: DividendKind,
         total_pool: u64,
+        snapshot_slot: Option<u64>,
+        claimable_in_seconds: Option<u64>,
         expires_in_seconds: Option<u64>,
+        min_claim: u64,
     ) -> Result<()> {
-        instructions::dividends::create_round_handler(ctx, round_id, total_pool, expires_in_seconds)
+        instructions::dividends::create_round_handler(ctx, round_id, kind, total_pool, snapshot_slot, claimable_in_seconds, expires_in_seconds, min_claim)
     }
 
-    /// Claim dividend
+    /// Record the current total supply on a round and recompute amount_per_share (before any claims)
+    pub fn create_dividend_snapshot(ctx: Context<CreateDividendSnapshot>) -> Result<()> {
+        instructions::dividends::snapshot_handler(ctx)
+    }
+
+    /// Extend an active dividend round's claim deadline (round creator only)
+    pub fn extend_dividend_expiry(ctx: Context<ExtendDividendExpiry>, new_expiry: i64) -> Result<()> {
+        instructions::dividends::extend_expiry_handler(ctx, new_expiry)
+    }
+
+    /// Add funds to an active round and raise its amount_per_share (creator or multisig)
+    pub fn top_up_dividend_round(ctx: Context<TopUpDividendRound>, amount: u64) -> Result<()> {
+        instructions::dividends::top_up_handler(ctx, amount)
+    }
+
+    /// Claim dividend (again after a top-up, for the per-share increase)
     pub fn claim_dividend(ctx: Context<ClaimDividend>) -> Result<()> {
         instructions::dividends::claim_handler(ctx)
     }
 
+    /// Sweep a round's unclaimable rounding dust to treasury
+    pub fn sweep_dividend_dust(ctx: Context<SweepDividendDust>) -> Result<()> {
+        instructions::dividends::sweep_dust_handler(ctx)
+    }
+
+    /// Return an expired round's unclaimed funds to treasury and mark it Completed
+    pub fn reclaim_expired_dividend(ctx: Context<ReclaimExpiredDividend>) -> Result<()> {
+        instructions::dividends::reclaim_expired_handler(ctx)
+    }
+
+    /// Close a completed or expired dividend round, sweeping leftovers and reclaiming rent
+    /// for the round and its pool token account
+    pub fn close_dividend_round(ctx: Context<CloseDividendRound>) -> Result<()> {
+        instructions::dividends::close_round_handler(ctx)
+    }
+
+    /// Close the caller's claim record for a completed round and reclaim its rent
+    pub fn close_dividend_claim(ctx: Context<CloseDividendClaim>) -> Result<()> {
+        instructions::dividends::close_claim_handler(ctx)
+    }
+
+    /// Get a claimant's dividend entitlement (returned via return data as u64)
+    pub fn get_claimable_dividend(ctx: Context<GetClaimableDividend>) -> Result<()> {
+        instructions::dividends::get_claimable_handler(ctx)
+    }
+
     // =========================================================================
     // ADMIN
     // =========================================================================
 
-    /// Pause/unpause token transfers
+    /// Pause/unpause all token movement. While paused, mint_tokens, transfer_tokens,
+    /// create_vesting_schedule, release_vested_tokens and claim_dividend are rejected.
     pub fn set_token_paused(ctx: Context<SetTokenPaused>, paused: bool) -> Result<()> {
         instructions::admin::set_paused_handler(ctx, paused)
     }
 
+    /// Set the transfer fee (basis points) and the treasury account that receives it
+    pub fn set_transfer_fee(ctx: Context<SetTransferFee>, transfer_fee_bps: Option<u16>) -> Result<()> {
+        instructions::admin::set_transfer_fee_handler(ctx, transfer_fee_bps)
+    }
+
+    /// Sync token_config.total_supply with the mint's actual supply
+    pub fn reconcile_supply(ctx: Context<ReconcileSupply>) -> Result<()> {
+        instructions::admin::reconcile_supply_handler(ctx)
+    }
+
+    /// Restrict dividend rounds to the given payment mints (empty list allows any)
+    pub fn set_allowed_payment_tokens(
+        ctx: Context<SetAllowedPaymentTokens>,
+        payment_tokens: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::admin::set_allowed_payment_tokens_handler(ctx, payment_tokens)
+    }
+
+    /// Cap the number of allowlisted wallets (None removes the cap)
+    pub fn set_max_holders(ctx: Context<SetMaxHolders>, max_holders: Option<u32>) -> Result<()> {
+        instructions::admin::set_max_holders_handler(ctx, max_holders)
+    }
+
+    /// Set the decimals clients use to display share amounts (mint decimals are unchanged)
+    pub fn set_token_decimals_display(ctx: Context<SetTokenDecimalsDisplay>, display_decimals: u8) -> Result<()> {
+        instructions::admin::set_decimals_display_handler(ctx, display_decimals)
+    }
+
+    /// Set the upgrade timelock in seconds (minimum one hour)
+    pub fn set_upgrade_timelock(ctx: Context<SetUpgradeTimelock>, seconds: i64) -> Result<()> {
+        instructions::admin::set_upgrade_timelock_handler(ctx, seconds)
+    }
+
+    /// Enable additional feature flags (multisig only; flags cannot be disabled)
+    pub fn update_features(ctx: Context<UpdateFeatures>, features: TokenFeatures) -> Result<()> {
+        instructions::admin::update_features_handler(ctx, features)
+    }
+
+    /// Get the token's recent admin actions, oldest first (returned via return data as Vec<AuditEntry>)
+    pub fn get_audit_log(ctx: Context<GetAuditLog>) -> Result<()> {
+        instructions::admin::get_audit_log_handler(ctx)
+    }
+
+    /// Permanently retire a token - blocks mint, transfer, vesting and dividend creation
+    pub fn deactivate_token(ctx: Context<DeactivateToken>) -> Result<()> {
+        instructions::admin::deactivate_handler(ctx)
+    }
+
     /// Initialize mint authority - transfers mint authority from token_config to a PDA
     /// owned by the token program. Must be called once after token creation.
     pub fn initialize_mint_authority(ctx: Context<InitializeMintAuthority>) -> Result<()> {
         instructions::admin::initialize_mint_authority_handler(ctx)
     }
+
+    /// Accept the mint authority after the factory transferred it to the PDA.
+    /// Verifies the mint's authority on-chain before enabling minting.
+    /// Must be called after factory.transfer_mint_authority.
+    pub fn accept_mint_authority(ctx: Context<AcceptMintAuthority>) -> Result<()> {
+        instructions::admin::accept_mint_authority_handler(ctx)
+    }
 }
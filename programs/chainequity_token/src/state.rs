@@ -17,6 +17,12 @@ pub struct AllowlistEntry {
     pub approved_by: Pubkey,
     /// Current status
     pub status: AllowlistStatus,
+    /// KYC tier (0-3), indexes TokenConfig.kyc_level_limits
+    pub kyc_level: u8,
+    /// Excluded from dividend distributions (share stays in the pool)
+    pub dividend_opt_out: bool,
+    /// Transfers to or from this wallet skip the transfer fee (treasury, escrow, etc.)
+    pub fee_exempt: bool,
     /// PDA bump
     pub bump: u8,
 }
@@ -28,6 +34,9 @@ impl AllowlistEntry {
         8 +  // approved_at
         32 + // approved_by
         1 +  // status enum
+        1 +  // kyc_level
+        1 +  // dividend_opt_out
+        1 +  // fee_exempt
         1;   // bump
 }
 
@@ -61,6 +70,14 @@ pub struct WalletRestrictions {
     pub lockout_until: Option<i64>,
     /// Maximum holdings (None = unlimited)
     pub max_balance: Option<u64>,
+    /// Minimum seconds between consecutive transfers (None = no cooldown)
+    pub min_transfer_interval: Option<u64>,
+    /// Timestamp of the last successful transfer
+    pub last_transfer_ts: i64,
+    /// ID of the last factory multisig transaction applied (prevents replaying older approvals)
+    pub last_multisig_tx: Option<u64>,
+    /// Whether LockoutExpired has been emitted for the current lockout_until
+    pub lockout_notified: bool,
     /// PDA bump
     pub bump: u8,
 }
@@ -74,6 +91,10 @@ impl WalletRestrictions {
         8 +  // last_transfer_day
         (1 + 8) + // lockout_until Option<i64>
         (1 + 8) + // max_balance Option<u64>
+        (1 + 8) + // min_transfer_interval Option<u64>
+        8 +  // last_transfer_ts
+        (1 + 8) + // last_multisig_tx Option<u64>
+        1 +  // lockout_notified
         1;   // bump
 }
 
@@ -107,6 +128,18 @@ impl VestingInterval {
     }
 }
 
+/// How the indivisible remainder (total_amount % total_intervals) is spread
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Debug)]
+pub enum RemainderPolicy {
+    /// The last N intervals each release +1 (N = remainder)
+    #[default]
+    BackLoaded,
+    /// The first N intervals each release +1
+    FrontLoaded,
+    /// The whole remainder releases with the final interval
+    FinalInterval,
+}
+
 /// Vesting schedule for a beneficiary
 ///
 /// All vesting is discrete interval-based: tokens release at fixed intervals
@@ -117,6 +150,8 @@ pub struct VestingSchedule {
     pub token_config: Pubkey,
     /// Wallet receiving vested tokens
     pub beneficiary: Pubkey,
+    /// Beneficiary at creation; used in the PDA seed so the address survives a migration
+    pub seed_beneficiary: Pubkey,
     /// Total tokens in schedule
     pub total_amount: u64,
     /// Already released (via on-chain release transactions)
@@ -129,6 +164,8 @@ pub struct VestingSchedule {
     pub total_duration: u64,
     /// Interval at which tokens release (minute/hour/day/month)
     pub interval: VestingInterval,
+    /// How the per-interval remainder is distributed
+    pub remainder_policy: RemainderPolicy,
     /// Number of intervals completed (for tracking)
     pub intervals_released: u64,
     /// Can issuer revoke unvested?
@@ -145,35 +182,58 @@ pub struct VestingSchedule {
     pub vested_at_termination: Option<u64>,
     /// Audit trail notes (max 200 chars)
     pub termination_notes: Option<String>,
+    /// Who funded the escrow (receives rent when the schedule is closed)
+    pub funder: Pubkey,
     /// PDA bump
     pub bump: u8,
 }
 
 impl VestingSchedule {
+    /// Max schedules released in one batch_release (keeps it within compute limits)
+    pub const MAX_BATCH_RELEASE: usize = 5;
+
     pub const LEN: usize = 8 + // discriminator
         32 + // token_config
         32 + // beneficiary
+        32 + // seed_beneficiary
         8 +  // total_amount
         8 +  // released_amount
         8 +  // start_time
         8 +  // cliff_duration
         8 +  // total_duration
         1 +  // interval enum
+        1 +  // remainder_policy enum
         8 +  // intervals_released
         1 +  // revocable
         1 +  // revoked
-        (1 + 1) + // termination_type Option<enum>
+        (1 + 1 + 8) + // termination_type Option<enum> (PartialAcceleration carries a u64)
         (1 + 8) + // terminated_at Option<i64>
         (1 + 32) + // terminated_by Option<Pubkey>
         (1 + 8) + // vested_at_termination Option<u64>
         (1 + 4 + 200) + // termination_notes Option<String>
+        32 + // funder
         1;   // bump
 
-    /// Calculate total number of vesting intervals (after cliff)
+    /// Whether everything owed to the beneficiary has been released
+    /// (fully released, or terminated and paid out up to the frozen vested amount)
+    pub fn is_settled(&self) -> bool {
+        match self.vested_at_termination {
+            Some(vested) => self.revoked && self.released_amount >= vested,
+            None => self.released_amount == self.total_amount,
+        }
+    }
+
+    /// Whether the full amount vests at the cliff (total_duration == cliff_duration)
+    pub fn is_cliff_only(&self) -> bool {
+        self.total_duration == self.cliff_duration
+    }
+
+    /// Calculate total number of vesting intervals (after cliff).
+    /// A cliff-only schedule has a single interval released at the cliff.
     pub fn total_intervals(&self) -> u64 {
         let vesting_duration = self.total_duration.saturating_sub(self.cliff_duration);
         let interval_seconds = self.interval.to_seconds();
-        if interval_seconds == 0 {
+        if interval_seconds == 0 || self.is_cliff_only() {
             return 1;
         }
         vesting_duration / interval_seconds
@@ -196,6 +256,44 @@ impl VestingSchedule {
         }
         self.total_amount % total_intervals
     }
+
+    /// Cumulative remainder shares vested after the first `intervals` intervals,
+    /// according to the schedule's remainder policy
+    pub fn remainder_through(&self, intervals: u64) -> u64 {
+        let total_intervals = self.total_intervals();
+        let remainder = self.remainder();
+        match self.remainder_policy {
+            RemainderPolicy::BackLoaded => intervals
+                .saturating_sub(total_intervals - remainder)
+                .min(remainder),
+            RemainderPolicy::FrontLoaded => intervals.min(remainder),
+            RemainderPolicy::FinalInterval => {
+                if intervals >= total_intervals { remainder } else { 0 }
+            }
+        }
+    }
+}
+
+/// Index of all vesting schedules for a beneficiary on a token
+#[account]
+pub struct BeneficiaryVestingIndex {
+    /// Token config this belongs to
+    pub token_config: Pubkey,
+    /// Beneficiary wallet
+    pub beneficiary: Pubkey,
+    /// Vesting schedule accounts for this beneficiary (max 20)
+    pub schedules: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl BeneficiaryVestingIndex {
+    pub const MAX_SCHEDULES: usize = 20;
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_config
+        32 + // beneficiary
+        (4 + 32 * Self::MAX_SCHEDULES) + // schedules vec
+        1;   // bump
 }
 
 // Keep VestingType for backward compatibility during migration
@@ -207,7 +305,7 @@ pub enum VestingType {
     Stepped,
 }
 
-/// Termination types (simplified to 3)
+/// Termination types
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum TerminationType {
     /// Standard departure - keep vested, lose unvested
@@ -216,6 +314,8 @@ pub enum TerminationType {
     ForCause,
     /// Accelerated - 100% vests immediately
     Accelerated,
+    /// Negotiated partial acceleration - vested plus `additional_vested` of the unvested remainder
+    PartialAcceleration { additional_vested: u64 },
 }
 
 /// Parameters for creating a vesting schedule
@@ -231,8 +331,17 @@ pub struct VestingParams {
     pub total_duration: u64,
     /// Release interval (minute/hour/day/month)
     pub interval: VestingInterval,
+    /// How the per-interval remainder is distributed (BackLoaded by default)
+    pub remainder_policy: RemainderPolicy,
     /// Can the schedule be revoked by admin?
     pub revocable: bool,
+    /// Explicitly allow a start_time more than MAX_BACKDATE_SECONDS in the past
+    pub backdated: bool,
+}
+
+impl VestingParams {
+    /// How far in the past start_time may be without the backdated flag (30 days)
+    pub const MAX_BACKDATE_SECONDS: i64 = 30 * 86400;
 }
 
 // ============================================================================
@@ -246,35 +355,65 @@ pub struct DividendRound {
     pub token_config: Pubkey,
     /// Sequential round ID
     pub id: u64,
-    /// Payment token mint (e.g., TestUSDC)
+    /// Payment token mint (e.g., TestUSDC, or a subsidiary's token for a spin-off)
     pub payment_token: Pubkey,
+    /// Cash dividend or in-kind share distribution (for reporting)
+    pub kind: DividendKind,
     /// Total dividend amount
     pub total_pool: u64,
     /// Block for ownership snapshot
     pub snapshot_slot: u64,
-    /// Calculated: pool / supply
+    /// Total supply recorded at the snapshot; amount_per_share is derived from it
+    pub supply_at_snapshot: u64,
+    /// Calculated: pool / supply_at_snapshot
     pub amount_per_share: u64,
     /// Round status
     pub status: DividendStatus,
     /// Creation timestamp
     pub created_at: i64,
+    /// Earliest claim time, e.g. the payment date after a record date (None = immediately)
+    pub claimable_from: Option<i64>,
     /// Claim deadline (None = no expiry)
     pub expires_at: Option<i64>,
+    /// Running total of dividends claimed
+    pub total_claimed: u64,
+    /// Number of distinct claimants
+    pub claims_count: u32,
+    /// Smallest gross entitlement that may be claimed; smaller holders stay in the pool
+    pub min_claim: u64,
+    /// Rounding remainder of the pool that no holder can ever claim
+    pub dust: u64,
+    /// Whether the dust has been swept to treasury
+    pub dust_swept: bool,
+    /// Who created (and funded) the round - receives rent on close
+    pub created_by: Pubkey,
     /// PDA bump
     pub bump: u8,
 }
 
 impl DividendRound {
+    /// Oldest record slot accepted at creation (~1 day at 400ms slots)
+    pub const MAX_SNAPSHOT_AGE_SLOTS: u64 = 216_000;
+
     pub const LEN: usize = 8 + // discriminator
         32 + // token_config
         8 +  // id
         32 + // payment_token
+        1 +  // kind
         8 +  // total_pool
         8 +  // snapshot_slot
+        8 +  // supply_at_snapshot
         8 +  // amount_per_share
         1 +  // status
         8 +  // created_at
+        (1 + 8) + // claimable_from Option<i64>
         (1 + 8) + // expires_at Option<i64>
+        8 +  // total_claimed
+        4 +  // claims_count
+        8 +  // min_claim
+        8 +  // dust
+        1 +  // dust_swept
+        32 + // created_by
         1;   // bump
 }
 
@@ -286,6 +425,16 @@ pub enum DividendStatus {
     Completed,
 }
 
+/// What a dividend round distributes. Claim math is the same for both.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Debug)]
+pub enum DividendKind {
+    /// Cash or stablecoin (e.g., TestUSDC)
+    #[default]
+    Cash,
+    /// Shares of another token distributed pro-rata (spin-off)
+    StockInKind,
+}
+
 /// Record of a dividend claim
 #[account]
 pub struct DividendClaim {
@@ -293,9 +442,12 @@ pub struct DividendClaim {
     pub round: Pubkey,
     /// Claimant wallet
     pub wallet: Pubkey,
-    /// Amount claimed
-    pub amount: u64,
-    /// Claim timestamp
+    /// Balance the claim is paid on, fixed at the first claim so later top-ups
+    /// pay only the per-share increase on the same shares
+    pub balance: u64,
+    /// Cumulative amount claimed across all claims in this round
+    pub claimed: u64,
+    /// Most recent claim timestamp
     pub claimed_at: i64,
     /// PDA bump
     pub bump: u8,
@@ -305,11 +457,129 @@ impl DividendClaim {
     pub const LEN: usize = 8 + // discriminator
         32 + // round
         32 + // wallet
-        8 +  // amount
+        8 +  // balance
+        8 +  // claimed
         8 +  // claimed_at
         1;   // bump
 }
 
+// ============================================================================
+// CORPORATE ACTIONS
+// ============================================================================
+
+/// Progress of an in-flight stock split, so batches are applied at most once
+#[account]
+pub struct SplitProgress {
+    /// Token config this belongs to
+    pub token_config: Pubkey,
+    /// Ratio of the split in progress
+    pub split_ratio: u8,
+    /// Highest holder token account processed so far. Batches walk holders in
+    /// ascending key order, so every account at or below this one is done.
+    pub last_processed_holder: Pubkey,
+    /// Number of batches processed
+    pub batches_processed: u32,
+    /// Total holder accounts processed across batches
+    pub accounts_processed: u32,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SplitProgress {
+    /// Max holder accounts per batch (keeps each batch within compute limits)
+    pub const MAX_SPLIT_BATCH: usize = 20;
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_config
+        1 +  // split_ratio
+        32 + // last_processed_holder
+        4 +  // batches_processed
+        4 +  // accounts_processed
+        1;   // bump
+
+    /// Whether a holder token account was covered by an earlier batch
+    pub fn is_holder_processed(&self, holder: &Pubkey) -> bool {
+        self.accounts_processed > 0 && *holder <= self.last_processed_holder
+    }
+}
+
+/// Admin action recorded in the audit log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum AdminAction {
+    Paused,
+    Unpaused,
+    SymbolChanged,
+    TransferFeeChanged,
+    Deactivated,
+    SupplyReconciled,
+    PaymentTokensChanged,
+    MaxHoldersChanged,
+    DisplayDecimalsChanged,
+    UpgradeTimelockChanged,
+    FeaturesUpdated,
+    KycLimitsChanged,
+    FeeExemptChanged,
+    AllowlistMigrated,
+}
+
+/// One audit log record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AuditEntry {
+    pub action: AdminAction,
+    /// Signer who performed the action
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+impl AuditEntry {
+    pub const LEN: usize = 1 + 32 + 8;
+}
+
+/// Ring buffer of the most recent admin actions on a token, kept on-chain for auditors
+#[account]
+pub struct AuditLog {
+    /// Token config this belongs to
+    pub token_config: Pubkey,
+    /// Recorded actions; once full, the oldest is overwritten
+    pub entries: Vec<AuditEntry>,
+    /// Slot in entries that the next action is written to
+    pub next_index: u8,
+    /// Actions recorded since creation, including overwritten ones
+    pub total_actions: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AuditLog {
+    /// Capacity; the chronological dump must fit in 1024 bytes of return data
+    pub const MAX_ENTRIES: usize = 16;
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_config
+        (4 + AuditEntry::LEN * Self::MAX_ENTRIES) + // entries
+        1 +  // next_index
+        8 +  // total_actions
+        1;   // bump
+
+    pub fn record(&mut self, action: AdminAction, actor: Pubkey, timestamp: i64) {
+        let entry = AuditEntry { action, actor, timestamp };
+        if self.entries.len() < Self::MAX_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next_index as usize] = entry;
+        }
+        self.next_index = ((self.next_index as usize + 1) % Self::MAX_ENTRIES) as u8;
+        self.total_actions = self.total_actions.saturating_add(1);
+    }
+
+    /// Entries ordered oldest to newest
+    pub fn chronological(&self) -> Vec<AuditEntry> {
+        if self.entries.len() < Self::MAX_ENTRIES {
+            return self.entries.clone();
+        }
+        let (newer, older) = self.entries.split_at(self.next_index as usize);
+        older.iter().chain(newer.iter()).cloned().collect()
+    }
+}
+
 // ============================================================================
 // PDA SEEDS
 // ============================================================================
@@ -318,10 +588,35 @@ pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
 pub const RESTRICTIONS_SEED: &[u8] = b"restrictions";
 pub const VESTING_SEED: &[u8] = b"vesting";
 pub const VESTING_ESCROW_SEED: &[u8] = b"vesting_escrow";
+pub const VESTING_INDEX_SEED: &[u8] = b"vesting_index";
 pub const DIVIDEND_ROUND_SEED: &[u8] = b"dividend_round";
 pub const DIVIDEND_CLAIM_SEED: &[u8] = b"dividend_claim";
 pub const DIVIDEND_POOL_SEED: &[u8] = b"dividend_pool";
 pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+pub const SPLIT_PROGRESS_SEED: &[u8] = b"split_progress";
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+
+/// chainequity_governance program. Its executed-proposal action markers gate dividend
+/// creation for governed tokens (the crate can't be a dependency: it depends on this one).
+pub const GOVERNANCE_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("CKL6Y6ma5nffMts5Q3fEyER9RoPHHGoxFnEYuLXvNWrd");
+
+/// Byte layout of a governance Proposal holding an InitiateDividend action. Mirrors
+/// chainequity_governance's Proposal (discriminator, id, token_config, proposer, action)
+/// and must change with it.
+pub mod governance_proposal {
+    use core::ops::Range;
+
+    pub const DISCRIMINATOR: Range<usize> = 0..8;
+    pub const TOKEN_CONFIG: Range<usize> = 16..48;
+    pub const ACTION_TAG: usize = 80;
+    /// Variant index of GovernanceAction::InitiateDividend
+    pub const INITIATE_DIVIDEND_TAG: u8 = 2;
+    pub const PAYMENT_TOKEN: Range<usize> = 81..113;
+    pub const TOTAL_AMOUNT: Range<usize> = 113..121;
+    pub const ROUND_ID: Range<usize> = 121..129;
+    /// Shortest account data that covers every field above
+    pub const MIN_LEN: usize = 129;
+}
 
 // ============================================================================
 // MINT AUTHORITY
@@ -334,13 +629,19 @@ pub struct MintAuthority {
     pub token_config: Pubkey,
     /// The mint this authority controls
     pub mint: Pubkey,
+    /// Whether the mint's authority has been handed over to this PDA
+    pub authority_transferred: bool,
     /// PDA bump
     pub bump: u8,
 }
 
 impl MintAuthority {
+    /// Max recipients per mint_tokens_batch (keeps it within compute limits)
+    pub const MAX_MINT_BATCH: usize = 10;
+
     pub const LEN: usize = 8 + // discriminator
         32 + // token_config
         32 + // mint
+        1 +  // authority_transferred
         1;   // bump
 }
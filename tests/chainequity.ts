@@ -122,6 +122,12 @@ describe("ChainEquity", () => {
       console.log("Transfer test placeholder");
     });
 
+    it("should reject allowlist migration by a non-admin", async () => {
+      // migrate_allowlist signed by a wallet outside the token multisig fails with
+      // Unauthorized and both allowlist entries are left untouched
+      console.log("Non-admin allowlist migration test placeholder");
+    });
+
     it("should reject transfer to non-allowlisted wallet", async () => {
       // Should fail when recipient not on allowlist
       console.log("Non-allowlist rejection test placeholder");
@@ -180,6 +186,23 @@ describe("ChainEquity", () => {
       // Double claim prevention test placeholder
       console.log("Double claim prevention test placeholder");
     });
+
+    it("should not persist a claim when the pool is underfunded", async () => {
+      // Mint after the round's snapshot so the last holder's entitlement exceeds what
+      // is left in the pool; claim_dividend fails and no DividendClaim persists for them
+      console.log("Underfunded pool claim test placeholder");
+    });
+
+    it("should not let a claim and a reclaim both drain an expired round", async () => {
+      // Send claim_dividend and reclaim_expired_dividend for the same expired round
+      // in one slot; exactly one succeeds and the vault never pays out twice
+      console.log("Claim/reclaim race test placeholder");
+    });
+
+    it("should reject reclaiming an expired dividend while paused", async () => {
+      // Pause the token, then reclaim_expired_dividend fails with TransfersPaused
+      console.log("Paused reclaim test placeholder");
+    });
   });
 
   describe("Governance", () => {
@@ -193,6 +216,12 @@ describe("ChainEquity", () => {
       console.log("Voting test placeholder");
     });
 
+    it("should cast and then withdraw a vote", async () => {
+      // cast_vote then withdraw_vote on a proposal derived from the governance config:
+      // the tally returns to zero, voter_count drops back and the vote record is closed
+      console.log("Vote withdrawal test placeholder");
+    });
+
     it("should execute passed proposals", async () => {
       // Proposal execution test placeholder
       console.log("Proposal execution test placeholder");